@@ -0,0 +1,241 @@
+use crate::helper::{
+    calc_draft_rounds, calc_pairings, calc_path_hashmap, format_bracket, format_draft,
+    write_history, write_report, Point, Round, SadariEnvironment, SetupState,
+};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+/// Steps of `path` in plain sentences, e.g. for screen readers. The path is
+/// made by `calc_path`, points of a row crossing one or more bridges and the
+/// last point at the bottom.
+pub fn calc_path_steps(path: &[Point], name_vec: &[String], result_vec: &[String]) -> Vec<String> {
+    let (first, last) = match (path.first(), path.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Vec::new(),
+    };
+    let name = |x: i32| name_vec.get(x as usize).map_or("", |name| name.as_str());
+
+    let mut steps = vec![format!(
+        "{} starts at the top of rail {}.",
+        name(first.x),
+        first.x + 1
+    )];
+
+    // a crossing is a run of points in the same row, longer than a bridge
+    // when the row is shared by the next gaps
+    let mut start = 0;
+    while start < path.len() - 1 {
+        let from = &path[start];
+        let end = path[start..path.len() - 1]
+            .iter()
+            .position(|point| point.y != from.y)
+            .map_or(path.len() - 1, |len| start + len);
+        let to = &path[end - 1];
+
+        if end - start > 1 {
+            steps.push(format!(
+                "Goes down to row {} of {}, crosses {} to {}'s rail.",
+                from.y + 1,
+                last.y,
+                if to.x > from.x { "right" } else { "left" },
+                name(to.x)
+            ));
+        }
+        start = end;
+    }
+
+    steps.push(format!(
+        "Goes down to the bottom of rail {} and gets {}.",
+        last.x + 1,
+        result_vec
+            .get(last.x as usize)
+            .map_or("", |result| result.as_str())
+    ));
+
+    steps
+}
+
+/// Final mapping as one line per player, in the order of names
+pub fn calc_result_lines(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    result_vec: &[String],
+) -> Vec<String> {
+    calc_pairings(path_hashmap)
+        .into_iter()
+        .map(|(name_index, result_index)| {
+            format!(
+                "{}: {}",
+                name_vec[name_index as usize], result_vec[result_index as usize]
+            )
+        })
+        .collect()
+}
+
+/// Reads names and results line by line, until they are valid
+fn read_accessible_input<R, W>(
+    input: &mut R,
+    out: &mut W,
+    sadari_env: SadariEnvironment,
+) -> io::Result<Option<SadariEnvironment>>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut read_line = |out: &mut W, prompt: &str| -> io::Result<Option<Vec<String>>> {
+        writeln!(out, "{}", prompt)?;
+        out.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            line.trim()
+                .split(',')
+                .map(|x| String::from(x.trim()))
+                .filter(|x| !x.is_empty())
+                .collect(),
+        ))
+    };
+
+    loop {
+        let name_vec = match read_line(out, "Names, separated by commas:")? {
+            Some(name_vec) => name_vec,
+            None => return Ok(None),
+        };
+        let result_vec = match read_line(out, "Results, separated by commas, empty for numbers:")? {
+            Some(result_vec) if result_vec.is_empty() => {
+                (0..name_vec.len()).map(|x| x.to_string()).collect()
+            }
+            Some(result_vec) => result_vec,
+            None => return Ok(None),
+        };
+
+        if name_vec.len() != result_vec.len() {
+            writeln!(
+                out,
+                "There are {} names and {} results, they should be the same.",
+                name_vec.len(),
+                result_vec.len()
+            )?;
+            continue;
+        }
+
+        let setup_state = SetupState {
+            name_vec,
+            result_vec,
+            ..SetupState::default()
+        };
+        match setup_state.validate() {
+            Ok(()) => return Ok(Some(setup_state.sadari_env(sadari_env).fill_mode_results())),
+            Err(message) => writeln!(out, "{}.", message)?,
+        };
+    }
+}
+
+/// Plays sadari in plain text instead of the ladder screen. Every line can be
+/// read by a screen reader in order.
+pub fn run_accessible<R, W>(
+    mut input: R,
+    mut out: W,
+    sadari_env: SadariEnvironment,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    // names and results are not given by file, ask them
+    let sadari_env = if sadari_env.name_vec.is_empty() {
+        match read_accessible_input(&mut input, &mut out, sadari_env)? {
+            Some(sadari_env) => sadari_env,
+            None => return Ok(()),
+        }
+    } else {
+        sadari_env
+    };
+
+    sadari_env
+        .check_constraints()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+    let mut sadari_env = sadari_env.fit_avoidance();
+    if let Some(note) = &sadari_env.avoidance_note {
+        writeln!(out, "Note: {}.", note)?;
+    }
+    let mut rng = sadari_env.rng();
+
+    loop {
+        let number_of_blocks = sadari_env.number_of_blocks;
+        let y_coordinate = sadari_env.geometry.rows;
+        let bridge_hashmap = sadari_env
+            .bridge_hashmap(&mut rng)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let path_hashmap = calc_path_hashmap(number_of_blocks, &bridge_hashmap, y_coordinate);
+
+        if sadari_env.tournament {
+            writeln!(out, "Round {}.", sadari_env.round)?;
+        }
+        writeln!(
+            out,
+            "Ladder with {} rails and {} rows.",
+            number_of_blocks, y_coordinate
+        )?;
+
+        for i in 0..number_of_blocks {
+            writeln!(out)?;
+            for step in calc_path_steps(
+                path_hashmap.get(&i).unwrap(),
+                &sadari_env.name_vec,
+                &sadari_env.result_vec,
+            ) {
+                writeln!(out, "{}", step)?;
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out, "Results:")?;
+        for line in calc_result_lines(&path_hashmap, &sadari_env.name_vec, &sadari_env.result_vec) {
+            writeln!(out, "{}", line)?;
+        }
+
+        if sadari_env.draft {
+            writeln!(out)?;
+            writeln!(out, "Draft order:")?;
+            let draft = calc_draft_rounds(&path_hashmap, &sadari_env.name_vec, sadari_env.snake);
+            for line in format_draft(&draft) {
+                writeln!(out, "{}", line)?;
+            }
+        }
+
+        // players who advanced play the next round
+        if sadari_env.tournament {
+            let round = Round::new(
+                sadari_env.round,
+                &path_hashmap,
+                &sadari_env.name_vec,
+                &sadari_env.result_vec,
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            writeln!(out)?;
+
+            if !round.is_final() {
+                sadari_env = sadari_env.next_round(round);
+                continue;
+            }
+
+            sadari_env.rounds.push(round);
+            writeln!(out, "Tournament:")?;
+            for line in format_bracket(&sadari_env.rounds) {
+                writeln!(out, "{}", line)?;
+            }
+        }
+        out.flush()?;
+
+        write_report(&sadari_env, &bridge_hashmap, &path_hashmap)?;
+        return write_history(&sadari_env, &path_hashmap);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+/// Time to draw one cell of the path at normal speed
+const STEP: Duration = Duration::from_millis(12);
+/// Animation speeds in percent, selected by `speed_index`
+const SPEEDS: [u32; 5] = [25, 50, 100, 200, 400];
+const NORMAL_SPEED_INDEX: usize = 2;
+
+/// Progress of the path animation. `tick` is the number of cells drawn along
+/// the path, and it is advanced by the elapsed time between frames, so the
+/// animation speed does not depend on the frame rate.
+#[derive(Debug)]
+pub struct Animation {
+    pub tick: i32,
+    pub paused: bool,
+    /// path is traced back from the result to the name
+    pub reverse: bool,
+    speed_index: usize,
+    elapsed: Duration,
+}
+
+impl Default for Animation {
+    fn default() -> Animation {
+        Animation {
+            tick: 0,
+            paused: false,
+            reverse: false,
+            speed_index: NORMAL_SPEED_INDEX,
+            elapsed: Duration::default(),
+        }
+    }
+}
+
+impl Animation {
+    pub fn new() -> Animation {
+        Animation::default()
+    }
+
+    /// Rewinds the animation, the speed is kept
+    pub fn reset(&mut self) {
+        self.tick = 0;
+        self.paused = false;
+        self.reverse = false;
+        self.elapsed = Duration::default();
+    }
+
+    /// Rewinds the animation to draw the path from the result to the name
+    pub fn trace_back(&mut self) {
+        self.reset();
+        self.reverse = true;
+    }
+
+    pub fn advance(&mut self, elapsed: Duration) {
+        if self.paused {
+            return;
+        }
+
+        self.elapsed += elapsed * self.speed() / 100;
+
+        let ticks = self.elapsed.as_millis() / STEP.as_millis();
+        self.tick = self.tick.saturating_add(ticks as i32);
+        self.elapsed -= STEP * ticks as u32;
+    }
+
+    /// Any tick beyond the path length draws the whole path
+    pub fn skip(&mut self) {
+        self.tick = i32::MAX;
+        self.paused = false;
+        self.elapsed = Duration::default();
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Pauses the animation and draws the path to the end of the current
+    /// segment. `segment_ends` are ticks where each segment of the path ends,
+    /// see `calc_segment_ends`.
+    pub fn step(&mut self, segment_ends: &[i32]) {
+        let path_length = segment_ends.last().copied().unwrap_or(0);
+        let next_end = segment_ends
+            .iter()
+            .copied()
+            .find(|end| *end > self.tick && *end < path_length);
+
+        self.tick = match next_end {
+            Some(end) => end,
+            // one more tick is needed to finish the last segment
+            None => self.tick.max(path_length + 1),
+        };
+        self.paused = true;
+        self.elapsed = Duration::default();
+    }
+
+    pub fn speed_up(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn speed_down(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    /// Current speed in percent of the normal speed
+    pub fn speed(&self) -> u32 {
+        SPEEDS[self.speed_index]
+    }
+}
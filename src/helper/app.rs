@@ -0,0 +1,402 @@
+use crate::helper::{
+    calc_clicked_index, calc_name_index_of_result, calc_next_index, calc_path_hashmap,
+    calc_path_length_in_area, calc_prev_index, calc_resized_tick, calc_result_layout,
+    calc_sadari_layout, calc_segment_ends, copy_to_clipboard, format_mapping, render_result,
+    render_sadari, render_setup, Action, Animation, EditAction, EditState, EditTarget, Event,
+    EventSource, Mouse, Point, RenderingState, ResultAction, ResultOrder, ResultState, Round,
+    SadariEnvironment, SetupAction, SetupState,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    io::Write,
+};
+use tui::{backend::Backend, layout::Rect, Terminal};
+
+/// Final ladder of the app, for the report and the history
+pub struct Outcome {
+    pub sadari_env: SadariEnvironment,
+    pub bridge_hashmap: HashMap<u16, Vec<u16>>,
+    pub path_hashmap: HashMap<u8, Vec<Point>>,
+    /// the result page was shown or every path was drawn, a draw nobody saw
+    /// is not written to the history
+    pub revealed: bool,
+}
+
+/// Setup screen, ladder and result pages driven by `events` until the user
+/// quits or the input is closed. `None` if the user quits in the setup.
+/// The clipboard is set through the backend, as it writes to the terminal.
+pub fn run_app<B, E>(
+    terminal: &mut Terminal<B>,
+    events: &mut E,
+    mut sadari_env: SadariEnvironment,
+) -> Result<Option<Outcome>, Box<dyn Error>>
+where
+    B: Backend + Write,
+    E: EventSource,
+{
+    // names and results are not given by file, fill them in the setup screen
+    if sadari_env.name_vec.is_empty() {
+        let mut setup_state = SetupState::new();
+        loop {
+            // results are the players themselves or team labels
+            if let Some(result_vec) = sadari_env.mode_results(&setup_state.name_vec) {
+                setup_state.result_vec = result_vec;
+            }
+            render_setup(terminal, &setup_state, &sadari_env)?;
+
+            // the setup is left when the input is closed
+            let event = match events.next() {
+                Some(event) => event,
+                None => return Ok(None),
+            };
+
+            if let Event::Input(key) = event {
+                match setup_state.handle_key(key) {
+                    SetupAction::Start => break,
+                    SetupAction::Quit => return Ok(None),
+                    SetupAction::Stay => {}
+                };
+            }
+        }
+
+        sadari_env = setup_state.sadari_env(sadari_env);
+        sadari_env.check_constraints()?;
+        sadari_env = sadari_env.fit_avoidance();
+    }
+
+    let mut number_of_blocks: u8 = sadari_env.number_of_blocks;
+    let y_coordinate = sadari_env.geometry.rows;
+
+    let mut rng = sadari_env.rng();
+    let mut bridge_hashmap = sadari_env.bridge_hashmap(&mut rng)?;
+    let mut path_hashmap = calc_path_hashmap(number_of_blocks, &bridge_hashmap, y_coordinate);
+
+    let mut selected_chunk = 0u8;
+    let mut animation = Animation::new();
+    let mut sadari_render_flag = true;
+    // prevent key event input while doing animation
+    let mut rendering_state = RenderingState::Idle;
+    // name or result box being edited in the sadari screen
+    let mut edit_state: Option<EditState> = None;
+    // last terminal area which was large enough to draw the ladder
+    let mut sadari_area = Rect::default();
+    // order, search and scroll of the result page
+    let mut result_state = ResultState::new();
+    if sadari_env.draft {
+        // players in the drawn order
+        result_state.order = ResultOrder::Result;
+    }
+
+    // names whose path was drawn to the end on the current ladder
+    let mut drawn_chunks: HashSet<u8> = HashSet::new();
+    let mut revealed = false;
+
+    let mut prev_frame = events.now();
+
+    loop {
+        if !sadari_render_flag {
+            revealed = true;
+
+            // render result pages
+            render_result(terminal, &sadari_env, &path_hashmap, &mut result_state)?;
+        } else {
+            // keep the animation progress when the terminal is resized
+            let area = terminal.size()?;
+            if area != sadari_area {
+                let path = path_hashmap.get(&selected_chunk).unwrap();
+                let path_length = |area: Rect| {
+                    calc_path_length_in_area(
+                        area,
+                        number_of_blocks,
+                        y_coordinate,
+                        path,
+                        selected_chunk,
+                    )
+                };
+
+                if let Some(length) = path_length(area) {
+                    if let Some(prev_length) = path_length(sadari_area) {
+                        animation.tick = calc_resized_tick(animation.tick, prev_length, length);
+                    }
+                    sadari_area = area;
+                }
+            }
+
+            render_sadari(
+                terminal,
+                &sadari_env,
+                selected_chunk,
+                &animation,
+                &mut rendering_state,
+                &bridge_hashmap,
+                &path_hashmap,
+                edit_state.as_ref(),
+            )?;
+
+            if rendering_state == RenderingState::Done {
+                drawn_chunks.insert(selected_chunk);
+                revealed |= drawn_chunks.len() == number_of_blocks as usize;
+            }
+        }
+
+        // frames are needed only while drawing the path
+        if rendering_state == RenderingState::Drawing && !animation.paused {
+            events.enable_frame();
+        } else {
+            events.disable_frame();
+        }
+
+        // e.g. the end of a script quits as the exit key does
+        let event = match events.next() {
+            Some(event) => event,
+            None => break,
+        };
+
+        if let Event::Frame = event {
+            let now = events.now();
+
+            // animation is paused while the terminal is too small
+            if rendering_state == RenderingState::Drawing && terminal.size()? == sadari_area {
+                animation.advance(now - prev_frame);
+            }
+            prev_frame = now;
+        }
+
+        match event {
+            Event::Mouse(Mouse::Press(x, y)) if !sadari_render_flag => {
+                let layout = calc_result_layout(terminal.size()?);
+                let rows = result_state.rows(&sadari_env, &path_hashmap);
+
+                if let Some(row) = result_state.row_at(&layout, &rows, x, y) {
+                    result_state.highlighted = match result_state.highlighted {
+                        Some(prev_row) if prev_row == row => None,
+                        _ => Some(row),
+                    };
+                }
+            }
+            Event::Mouse(Mouse::ScrollUp(_, _)) if !sadari_render_flag => {
+                result_state.scroll_up(1);
+            }
+            Event::Mouse(Mouse::ScrollDown(_, _)) if !sadari_render_flag => {
+                result_state.scroll_down(1);
+            }
+            Event::Mouse(mouse) if edit_state.is_none() => {
+                let layout = match calc_sadari_layout(
+                    terminal.size()?,
+                    number_of_blocks,
+                    y_coordinate,
+                    sadari_env.text_width(),
+                ) {
+                    Ok(layout) => layout,
+                    Err(_) => continue,
+                };
+
+                match mouse {
+                    Mouse::Press(x, y) => {
+                        let clicked_name = calc_clicked_index(&layout.name_rects, x, y);
+                        // a click on the result box traces back to its name
+                        let clicked_result = calc_clicked_index(&layout.result_rects, x, y)
+                            .and_then(|result_index| {
+                                calc_name_index_of_result(&path_hashmap, result_index)
+                            });
+
+                        if let Some(index) = clicked_name.or(clicked_result) {
+                            selected_chunk = index;
+                            if clicked_name.is_some() {
+                                animation.reset();
+                            } else {
+                                animation.trace_back();
+                            }
+                            rendering_state = RenderingState::Drawing;
+                            prev_frame = events.now();
+                        }
+                    }
+                    Mouse::ScrollUp(_, _) | Mouse::ScrollDown(_, _)
+                        if rendering_state != RenderingState::Drawing =>
+                    {
+                        rendering_state = RenderingState::Idle;
+                        animation.reset();
+
+                        selected_chunk = match mouse {
+                            Mouse::ScrollUp(_, _) => {
+                                calc_prev_index(selected_chunk, number_of_blocks)
+                            }
+                            _ => calc_next_index(selected_chunk, number_of_blocks),
+                        };
+                    }
+                    _ => {}
+                };
+            }
+            _ => {}
+        };
+
+        if let Event::Input(key) = event {
+            if !sadari_render_flag {
+                match result_state.handle_key(key, &sadari_env.keymap) {
+                    ResultAction::Stay => continue,
+                    ResultAction::Copy(format) => {
+                        let text = format_mapping(
+                            format,
+                            &path_hashmap,
+                            &sadari_env.name_vec,
+                            &sadari_env.result_vec,
+                        );
+                        copy_to_clipboard(terminal.backend_mut(), &text)?;
+                        result_state.notice = Some(format!(
+                            "Copied {} pairings to the clipboard as {}",
+                            path_hashmap.len(),
+                            format.name()
+                        ));
+
+                        continue;
+                    }
+                    ResultAction::Ignored => {}
+                };
+            }
+
+            if let Some(state) = edit_state.as_mut() {
+                match state.handle_key(key) {
+                    EditAction::Apply => {
+                        state.apply(&mut sadari_env);
+                        edit_state = None;
+                    }
+                    EditAction::Cancel => {
+                        edit_state = None;
+                    }
+                    EditAction::Stay => {}
+                };
+
+                continue;
+            }
+
+            let action = match sadari_env.keymap.action(key) {
+                Some(action) => action,
+                None => continue,
+            };
+
+            match action {
+                Action::Quit => {
+                    break;
+                }
+                Action::ToggleResult => {
+                    sadari_render_flag = !sadari_render_flag;
+                }
+                Action::Prev | Action::Next if sadari_render_flag => {
+                    match rendering_state {
+                        RenderingState::Idle | RenderingState::Done => {
+                            rendering_state = RenderingState::Idle;
+                            animation.reset();
+
+                            selected_chunk = match action {
+                                Action::Prev => calc_prev_index(selected_chunk, number_of_blocks),
+                                _ => calc_next_index(selected_chunk, number_of_blocks),
+                            };
+                        }
+                        RenderingState::Drawing => {}
+                    };
+                }
+                Action::Start if sadari_render_flag => {
+                    match rendering_state {
+                        RenderingState::Idle => {
+                            rendering_state = RenderingState::Drawing;
+                            prev_frame = events.now();
+                        }
+                        // fast-forward to the end of the animation
+                        RenderingState::Drawing => animation.skip(),
+                        RenderingState::Done => {}
+                    };
+                }
+                Action::FastForward if rendering_state == RenderingState::Drawing => {
+                    animation.skip();
+                }
+                Action::Pause if rendering_state == RenderingState::Drawing => {
+                    animation.toggle_pause();
+                    // time while paused should not be drawn
+                    prev_frame = events.now();
+                }
+                Action::Step if sadari_render_flag && rendering_state != RenderingState::Done => {
+                    // segments are measured in the last area the ladder was drawn
+                    if let Ok(layout) = calc_sadari_layout(
+                        sadari_area,
+                        number_of_blocks,
+                        y_coordinate,
+                        sadari_env.text_width(),
+                    ) {
+                        let segment_ends = calc_segment_ends(
+                            &layout.bridge_point_hashmap,
+                            path_hashmap.get(&selected_chunk).unwrap(),
+                            selected_chunk,
+                            animation.reverse,
+                        );
+
+                        animation.step(&segment_ends);
+                        rendering_state = RenderingState::Drawing;
+                    }
+                }
+                Action::SpeedUp => animation.speed_up(),
+                Action::SpeedDown => animation.speed_down(),
+                // results of the modes follow the players, e.g. teams
+                Action::EditResult if sadari_env.mode_results(&sadari_env.name_vec).is_some() => {}
+                Action::EditName | Action::EditResult if sadari_render_flag => {
+                    let target = match action {
+                        Action::EditName => EditTarget::Name,
+                        _ => EditTarget::Result,
+                    };
+
+                    edit_state = Some(EditState::new(target, selected_chunk, &sadari_env));
+                }
+                Action::NewLadder if sadari_render_flag => {
+                    // re-roll the ladder with the same names and results
+                    bridge_hashmap = sadari_env.bridge_hashmap(&mut rng)?;
+                    path_hashmap =
+                        calc_path_hashmap(number_of_blocks, &bridge_hashmap, y_coordinate);
+
+                    drawn_chunks.clear();
+                    revealed = false;
+                    rendering_state = RenderingState::Idle;
+                    animation.reset();
+                }
+                Action::NextRound if sadari_env.tournament && sadari_env.winner().is_none() => {
+                    let round = Round::new(
+                        sadari_env.round,
+                        &path_hashmap,
+                        &sadari_env.name_vec,
+                        &sadari_env.result_vec,
+                    )?;
+
+                    if round.is_final() {
+                        // the result page shows every round and the winner
+                        sadari_env.rounds.push(round);
+                        sadari_render_flag = false;
+                        continue;
+                    }
+
+                    // players who advanced play the next ladder
+                    sadari_env = sadari_env.next_round(round);
+                    number_of_blocks = sadari_env.number_of_blocks;
+                    bridge_hashmap = sadari_env.bridge_hashmap(&mut rng)?;
+                    path_hashmap =
+                        calc_path_hashmap(number_of_blocks, &bridge_hashmap, y_coordinate);
+
+                    drawn_chunks.clear();
+                    revealed = false;
+                    selected_chunk = 0;
+                    sadari_area = Rect::default();
+                    result_state = ResultState::new();
+                    sadari_render_flag = true;
+                    rendering_state = RenderingState::Idle;
+                    animation.reset();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Some(Outcome {
+        sadari_env,
+        bridge_hashmap,
+        path_hashmap,
+        revealed,
+    }))
+}
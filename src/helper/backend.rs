@@ -0,0 +1,96 @@
+// Terminal backend selected by cargo features. `termion` is the default,
+// `crossterm` is used instead when its feature is enabled.
+
+#[cfg(not(any(feature = "termion", feature = "crossterm")))]
+compile_error!("either `termion` or `crossterm` feature should be enabled");
+
+#[cfg(all(feature = "termion", not(feature = "crossterm")))]
+pub use self::termion_backend::*;
+
+#[cfg(feature = "crossterm")]
+pub use self::crossterm_backend::*;
+
+#[cfg(all(feature = "termion", not(feature = "crossterm")))]
+mod termion_backend {
+    use std::{
+        error::Error,
+        io::{self, Stdout},
+    };
+    use termion::{
+        input::MouseTerminal,
+        raw::{IntoRawMode, RawTerminal},
+        screen::AlternateScreen,
+    };
+    use tui::{backend::TermionBackend, Terminal};
+
+    pub type SadariBackend = TermionBackend<AlternateScreen<MouseTerminal<RawTerminal<Stdout>>>>;
+
+    /// Terminal in raw mode, alternate screen and mouse capture, all of them
+    /// are restored on drop
+    pub fn create_terminal() -> Result<Terminal<SadariBackend>, Box<dyn Error>> {
+        let stdout = io::stdout().into_raw_mode()?;
+        let stdout = MouseTerminal::from(stdout);
+        let stdout = AlternateScreen::from(stdout);
+        let backend = TermionBackend::new(stdout);
+
+        Ok(Terminal::new(backend)?)
+    }
+}
+
+#[cfg(feature = "crossterm")]
+mod crossterm_backend {
+    use crossterm::{
+        cursor::Show,
+        event::{DisableMouseCapture, EnableMouseCapture},
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+        ExecutableCommand,
+    };
+    use std::{
+        error::Error,
+        io::{self, Stdout, Write},
+    };
+    use tui::{backend::CrosstermBackend, Terminal};
+
+    pub type SadariBackend = CrosstermBackend<TerminalScreen>;
+
+    /// Terminal in raw mode, alternate screen and mouse capture, all of them
+    /// are restored on drop
+    pub fn create_terminal() -> Result<Terminal<SadariBackend>, Box<dyn Error>> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        stdout
+            .execute(EnterAlternateScreen)?
+            .execute(EnableMouseCapture)?;
+
+        Ok(Terminal::new(CrosstermBackend::new(TerminalScreen {
+            stdout,
+        }))?)
+    }
+
+    /// Stdout which restores the terminal on drop, like the screen wrappers
+    /// of termion
+    pub struct TerminalScreen {
+        stdout: Stdout,
+    }
+
+    impl Write for TerminalScreen {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.stdout.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.stdout.flush()
+        }
+    }
+
+    impl Drop for TerminalScreen {
+        fn drop(&mut self) {
+            let _ = self
+                .stdout
+                .execute(DisableMouseCapture)
+                .and_then(|stdout| stdout.execute(LeaveAlternateScreen))
+                .and_then(|stdout| stdout.execute(Show));
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+}
@@ -1,15 +1,66 @@
-use crate::helper::{LineDirection, Point};
-use rand::{rngs::ThreadRng, seq::IteratorRandom, Rng};
-use std::{
-    cmp::min,
-    collections::{HashMap, HashSet},
-    iter::FromIterator,
+use crate::helper::{Geometry, LineDirection, Point, ResultLayout};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
 };
+use std::{cmp::min, collections::HashMap};
 use tui::layout::Rect;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 type ChunkIndex = u16;
 type BridgeIndex = u16;
 
+const HORIZONTAL_MARGIN: u16 = 10;
+const MIN_GUIDE_HEIGHT: u16 = 8;
+const MIN_BOX_WIDTH: u16 = 3;
+const MIN_BOX_HEIGHT: u16 = 3;
+const BLOCK_WIDTH_RATIO: u8 = 3;
+const SPACE_WIDTH_RATIO: u8 = 1;
+
+/// Areas of the sadari screen. Rails and bridges depend only on the terminal
+/// size, so the same layout can be used for drawing and for re-calculating
+/// the animation. Boxes are widened to fit the text when there is room.
+#[derive(Debug)]
+pub struct SadariLayout {
+    pub guide: Rect,
+    pub footer: Rect,
+    pub name_rects: Vec<Rect>,
+    pub result_rects: Vec<Rect>,
+    pub rail_rects: Vec<Rect>,
+    /// rows where bridges can be drawn, `bridge_rects[chunk][bridge]`
+    pub bridge_rects: Vec<Vec<Rect>>,
+    /// logical point of the ladder -> terminal coordinate
+    pub bridge_point_hashmap: HashMap<Point, Point>,
+}
+
+/// Index of the pairing row at terminal coordinate (`x`, `y`) in the result page
+pub fn calc_result_row_index(
+    layout: &ResultLayout,
+    number_of_rows: u8,
+    x: u16,
+    y: u16,
+) -> Option<u8> {
+    let first = layout.columns.first()?;
+    let last = layout.columns.last()?;
+
+    if x < first.x || x >= last.right() || y < first.y || y >= first.bottom() {
+        return None;
+    }
+
+    let row = y - first.y;
+    Some(row / 2)
+        .filter(|index| row % 2 == 0 && *index < number_of_rows as u16)
+        .map(|index| index as u8)
+}
+
+/// Index of the rect containing terminal coordinate (`x`, `y`)
+pub fn calc_clicked_index(rects: &[Rect], x: u16, y: u16) -> Option<u8> {
+    rects
+        .iter()
+        .position(|rect| x >= rect.x && x < rect.right() && y >= rect.y && y < rect.bottom())
+        .map(|index| index as u8)
+}
+
 pub fn calc_names_layout(
     number_of_blocks: u8,
     block_width_ratio: u8,
@@ -31,20 +82,331 @@ pub fn calc_names_layout(
     let space_width = (space_width_ratio * unit_width) as u16;
 
     let vec: Vec<u16> = (0..number_of_blocks + 1)
-        .into_iter()
-        .map(|x| match x {
+        .flat_map(|x| match x {
             0 => vec![left_margin as u16],
-            num if num <= number_of_blocks - 1 && num > 0 => vec![block_width, space_width],
+            num if num < number_of_blocks && num > 0 => vec![block_width, space_width],
             _ => vec![block_width, right_margin as u16],
         })
-        .flatten()
         .collect::<Vec<u16>>();
 
     Ok(vec)
 }
 
-pub fn calc_bridge_indexes(
-    rng: &mut ThreadRng,
+/// Splits `area` horizontally by `vec_layout` percentages without rounding
+/// errors, so that every block width is at least `area.width * ratio / 100`
+fn calc_horizontal_chunks(area: Rect, vec_layout: &[u16]) -> Vec<Rect> {
+    let mut vec = Vec::new();
+    let mut acc: u32 = 0;
+
+    for percentage in vec_layout {
+        let left = area.width as u32 * acc / 100;
+        acc += *percentage as u32;
+        let right = area.width as u32 * acc / 100;
+
+        vec.push(Rect::new(
+            area.x + left as u16,
+            area.y,
+            (right - left) as u16,
+            area.height,
+        ));
+    }
+
+    vec
+}
+
+/// (guide height, main height, footer height) for the terminal `height`
+fn calc_vertical_layout(height: u16) -> (u16, u16, u16) {
+    let percent = |ratio: u32| (height as u32 * ratio / 100) as u16;
+    let guide_height = percent(15).max(MIN_GUIDE_HEIGHT).min(height);
+    // footer has one line at least for the detail of truncated texts
+    let footer_height = percent(5).max(1);
+    let main_height = height.saturating_sub(guide_height + footer_height);
+
+    (guide_height, main_height, footer_height)
+}
+
+/// (box height, rail height) for the main chunk `height`
+fn calc_main_layout(height: u16) -> (u16, u16) {
+    let box_height = (height / 10).max(MIN_BOX_HEIGHT);
+    let rail_height = height.saturating_sub(box_height * 2);
+
+    (box_height, rail_height)
+}
+
+/// Smallest (width, height) of the terminal which can draw the sadari screen
+pub fn calc_min_terminal_size(number_of_blocks: u8, y_coordinate: u16) -> (u16, u16) {
+    let block_width = calc_names_layout(number_of_blocks, BLOCK_WIDTH_RATIO, SPACE_WIDTH_RATIO)
+        .ok()
+        .and_then(|vec| vec.get(1).cloned())
+        .unwrap_or(1)
+        .max(1);
+    let min_width = HORIZONTAL_MARGIN * 2 + (MIN_BOX_WIDTH * 100).div_ceil(block_width);
+
+    // every bridge row needs one line at least, plus a line above and below
+    let min_rail_height = y_coordinate as u32 + 3;
+
+    // main height with boxes of `MIN_BOX_HEIGHT`, or else of a tenth of the
+    // height, `10k + j` has a rail of `8k + j`
+    let min_box_height = MIN_BOX_HEIGHT as u32;
+    let min_main_height = if min_rail_height + 2 * min_box_height < 10 * min_box_height {
+        min_rail_height + 2 * min_box_height
+    } else {
+        let tens = min_rail_height
+            .saturating_sub(9)
+            .div_ceil(8)
+            .max(min_box_height);
+        10 * tens + min_rail_height.saturating_sub(8 * tens)
+    };
+
+    // guide and footer take 20% and 9 lines at least, rounding down the
+    // percentages leaves the terminal height a couple of lines above the bound
+    let lower_bound = (min_main_height + MIN_GUIDE_HEIGHT as u32 + 1)
+        .max((min_main_height.saturating_sub(2) * 100).div_ceil(80))
+        .min(u16::MAX as u32) as u16;
+    let min_height = (lower_bound..=u16::MAX)
+        .find(|height| {
+            let (_, main_height, _) = calc_vertical_layout(*height);
+            let (_, rail_height) = calc_main_layout(main_height);
+
+            rail_height as u32 >= min_rail_height
+        })
+        .unwrap_or(u16::MAX);
+
+    (min_width, min_height)
+}
+
+/// Calculates areas of the sadari screen, `Err` has the minimum terminal size
+/// if `area` is too small to draw the ladder. `text_width` is the display
+/// width of the widest name or result, see `calc_max_text_width`.
+pub fn calc_sadari_layout(
+    area: Rect,
+    number_of_blocks: u8,
+    y_coordinate: u16,
+    text_width: u16,
+) -> Result<SadariLayout, (u16, u16)> {
+    let min_size = calc_min_terminal_size(number_of_blocks, y_coordinate);
+    if area.width < min_size.0 || area.height < min_size.1 {
+        return Err(min_size);
+    }
+
+    let (guide_height, main_height, footer_height) = calc_vertical_layout(area.height);
+    let guide = Rect::new(area.x, area.y, area.width, guide_height);
+    let footer = Rect::new(
+        area.x,
+        area.y + guide_height + main_height,
+        area.width,
+        footer_height,
+    );
+
+    let (box_height, rail_height) = calc_main_layout(main_height);
+    let main_x = area.x + HORIZONTAL_MARGIN;
+    let main_width = area.width - HORIZONTAL_MARGIN * 2;
+    let name_chunk = Rect::new(main_x, guide.bottom(), main_width, box_height);
+    let result_chunk = Rect::new(
+        main_x,
+        name_chunk.bottom() + rail_height,
+        main_width,
+        box_height,
+    );
+
+    let vec_names_layout =
+        calc_names_layout(number_of_blocks, BLOCK_WIDTH_RATIO, SPACE_WIDTH_RATIO)
+            .map_err(|_| min_size)?;
+
+    // odd chunks are blocks, even chunks are margins and spaces
+    let pick_blocks = |chunks: Vec<Rect>| -> Vec<Rect> {
+        chunks
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| i % 2 == 1)
+            .map(|(_, rect)| rect)
+            .collect()
+    };
+    let name_rects = pick_blocks(calc_horizontal_chunks(name_chunk, &vec_names_layout));
+    let result_rects = pick_blocks(calc_horizontal_chunks(result_chunk, &vec_names_layout));
+
+    let rail_rects: Vec<Rect> = name_rects
+        .iter()
+        .zip(result_rects.iter())
+        .map(|(n, r)| {
+            Rect::new(
+                n.x + n.width / 2,
+                n.bottom(),
+                (n.width / 2).max(1),
+                r.y.saturating_sub(n.bottom()),
+            )
+        })
+        .collect();
+
+    let name_rects = calc_widened_rects(&name_rects, &rail_rects, text_width, area);
+    let result_rects = calc_widened_rects(&result_rects, &rail_rects, text_width, area);
+
+    let mut bridge_point_hashmap: HashMap<Point, Point> = HashMap::new();
+    for (i, rail) in rail_rects.iter().enumerate() {
+        bridge_point_hashmap.insert(
+            Point::new(i as i32, -1),
+            Point::new(rail.x as i32, rail.y as i32 - 1),
+        );
+        bridge_point_hashmap.insert(
+            Point::new(i as i32, y_coordinate as i32),
+            Point::new(rail.x as i32, rail.bottom() as i32),
+        );
+    }
+
+    let bridge_rects: Vec<Vec<Rect>> = rail_rects
+        .windows(2)
+        .enumerate()
+        .map(|(i, rails)| {
+            let (left, right) = (rails[0], rails[1]);
+            let bridge_chunk = Rect::new(
+                left.x + 1,
+                left.y + 1,
+                right.x.saturating_sub(left.x + 1),
+                left.height.saturating_sub(2),
+            );
+
+            let mut y = bridge_chunk.y;
+            calc_distributed_height(y_coordinate + 1, bridge_chunk.height)
+                .iter()
+                .enumerate()
+                .map(|(bridge_index, height)| {
+                    let rect = Rect::new(bridge_chunk.x, y, bridge_chunk.width, *height);
+                    y += height;
+
+                    // bridge is drawn on the bottom line of its row, the
+                    // last rect is the space above the result boxes
+                    if bridge_index < y_coordinate as usize {
+                        let point_y = rect.bottom() as i32 - 1;
+                        bridge_point_hashmap.insert(
+                            Point::new(i as i32, bridge_index as i32),
+                            Point::new(left.x as i32, point_y),
+                        );
+                        bridge_point_hashmap.insert(
+                            Point::new(i as i32 + 1, bridge_index as i32),
+                            Point::new(right.x as i32, point_y),
+                        );
+                    }
+
+                    rect
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(SadariLayout {
+        guide,
+        footer,
+        name_rects,
+        result_rects,
+        rail_rects,
+        bridge_rects,
+        bridge_point_hashmap,
+    })
+}
+
+/// Widens boxes to fit `text_width` and the borders, centered on their rails.
+/// Boxes keep one column between each other and stay in `area`.
+fn calc_widened_rects(
+    rects: &[Rect],
+    rail_rects: &[Rect],
+    text_width: u16,
+    area: Rect,
+) -> Vec<Rect> {
+    let min_gap = rail_rects
+        .windows(2)
+        .map(|rails| rails[1].x - rails[0].x)
+        .min()
+        .unwrap_or(area.width);
+
+    rects
+        .iter()
+        .zip(rail_rects.iter())
+        .map(|(rect, rail)| {
+            let width = text_width
+                .saturating_add(2)
+                .min(min_gap.saturating_sub(1))
+                .min((rail.x - area.x).min(area.right() - rail.x) * 2);
+            if width <= rect.width {
+                return *rect;
+            }
+
+            Rect::new(rail.x - width / 2, rect.y, width, rect.height)
+        })
+        .collect()
+}
+
+/// Display width of `text`, wide characters take two columns
+pub fn calc_text_width(text: &str) -> u16 {
+    text.width().min(u16::MAX as usize) as u16
+}
+
+/// Display width of the widest text
+pub fn calc_max_text_width<'a, I>(texts: I) -> u16
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    texts
+        .into_iter()
+        .map(|text| calc_text_width(text))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Cuts `text` to `width` columns, the end is replaced with an ellipsis
+pub fn calc_truncated_text(text: &str, width: u16) -> String {
+    let width = width as usize;
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if truncated_width + char_width + 1 > width {
+            break;
+        }
+
+        truncated.push(c);
+        truncated_width += char_width;
+    }
+
+    if width > 0 {
+        truncated.push('…');
+    }
+
+    truncated
+}
+
+/// Cuts `text` to `width` columns keeping its end, e.g. for the text being
+/// edited, the beginning is replaced with an ellipsis
+pub fn calc_truncated_text_end(text: &str, width: u16) -> String {
+    let width = width as usize;
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let mut chars = Vec::new();
+    let mut truncated_width = 0;
+    for c in text.chars().rev() {
+        let char_width = c.width().unwrap_or(0);
+        if truncated_width + char_width + 1 > width {
+            break;
+        }
+
+        chars.push(c);
+        truncated_width += char_width;
+    }
+
+    if width > 0 {
+        chars.push('…');
+    }
+
+    chars.into_iter().rev().collect()
+}
+
+pub fn calc_bridge_indexes<R: Rng + ?Sized>(
+    rng: &mut R,
     number_of_bridge: u8,
     vec_candidates: Vec<BridgeIndex>,
 ) -> Vec<BridgeIndex> {
@@ -58,11 +420,7 @@ pub fn calc_bridge_indexes(
 pub fn calc_distributed_height(number_of_bridge: u16, height: u16) -> Vec<u16> {
     let bridge_height: u16 = height / number_of_bridge;
     let extra_bridges = height % number_of_bridge;
-    let space = if extra_bridges == 0 {
-        0
-    } else {
-        (number_of_bridge / extra_bridges) as usize
-    };
+    let space = number_of_bridge.checked_div(extra_bridges).unwrap_or(0) as usize;
 
     let mut vec = vec![bridge_height; number_of_bridge as usize];
     let mut index: usize = 0;
@@ -74,37 +432,483 @@ pub fn calc_distributed_height(number_of_bridge: u16, height: u16) -> Vec<u16> {
     vec
 }
 
-pub fn calc_bridge_hashmap(
+/// Gap on either side of `gap` which has a bridge in `row`
+fn calc_shared_neighbour(
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    gap: ChunkIndex,
+    row: BridgeIndex,
+) -> Option<ChunkIndex> {
+    [gap.checked_sub(1), gap.checked_add(1)]
+        .iter()
+        .flatten()
+        .find(|neighbour| {
+            bridge_hashmap
+                .get(neighbour)
+                .is_some_and(|vec| vec.contains(&row))
+        })
+        .copied()
+}
+
+/// Why `row` of `gap` cannot have a bridge, `None` if it is valid there.
+/// Without `geometry.share_rows`, gaps on both sides should not have the row.
+fn calc_bridge_conflict(
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
     number_of_blocks: u8,
-    number_of_max_bridges: u8,
-    y_coordinate: u16,
-    rng: &mut ThreadRng,
+    geometry: &Geometry,
+    gap: ChunkIndex,
+    row: BridgeIndex,
+) -> Option<String> {
+    if gap >= number_of_blocks.saturating_sub(1) as ChunkIndex {
+        Some(format!(
+            "gap {} is out of the ladder of {} rails",
+            gap, number_of_blocks
+        ))
+    } else if !geometry.bridge_rows().contains(&row) {
+        Some(format!(
+            "row {} of gap {} is out of rows {:?}",
+            row,
+            gap,
+            geometry.bridge_rows()
+        ))
+    } else if geometry.share_rows {
+        None
+    } else {
+        calc_shared_neighbour(bridge_hashmap, gap, row).map(|neighbour| {
+            format!(
+                "gaps {} and {} share row {}",
+                gap.min(neighbour),
+                gap.max(neighbour),
+                row
+            )
+        })
+    }
+}
+
+/// Puts a bridge in `row` of `gap` keeping the rows sorted, `Err` if the
+/// ladder would not be valid
+pub fn add_bridge(
+    bridge_hashmap: &mut HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    gap: ChunkIndex,
+    row: BridgeIndex,
+) -> Result<(), String> {
+    if let Some(conflict) =
+        calc_bridge_conflict(bridge_hashmap, number_of_blocks, geometry, gap, row)
+    {
+        return Err(conflict);
+    }
+
+    let vec = bridge_hashmap.entry(gap).or_default();
+    match vec.binary_search(&row) {
+        Ok(_) => Err(format!("gap {} already has row {}", gap, row)),
+        Err(index) => {
+            vec.insert(index, row);
+            Ok(())
+        }
+    }
+}
+
+/// Whether the ladder is valid for `geometry`. Every gap should have sorted
+/// rows which can have a bridge, and gaps on both sides of it should not
+/// have the same rows unless `geometry.share_rows`.
+pub fn validate_bridges(
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    number_of_blocks: u8,
+    geometry: &Geometry,
+) -> Result<(), String> {
+    let number_of_gaps = number_of_blocks.saturating_sub(1) as ChunkIndex;
+
+    if let Some(gap) = bridge_hashmap.keys().find(|gap| **gap >= number_of_gaps) {
+        return Err(format!(
+            "gap {} is out of the ladder of {} rails",
+            gap, number_of_blocks
+        ));
+    }
+
+    for gap in 0..number_of_gaps {
+        let vec = bridge_hashmap
+            .get(&gap)
+            .ok_or_else(|| format!("gap {} is missing", gap))?;
+
+        if vec.windows(2).any(|rows| rows[0] >= rows[1]) {
+            return Err(format!("rows of gap {} are not sorted: {:?}", gap, vec));
+        }
+
+        if vec.len() < geometry.min_bridges as usize {
+            return Err(format!(
+                "gap {} has {} bridges, fewer than min bridges {}",
+                gap,
+                vec.len(),
+                geometry.min_bridges
+            ));
+        }
+        if vec.len() > geometry.max_bridges as usize {
+            return Err(format!(
+                "gap {} has {} bridges, more than max bridges {}",
+                gap,
+                vec.len(),
+                geometry.max_bridges
+            ));
+        }
+
+        for row in vec {
+            if let Some(conflict) =
+                calc_bridge_conflict(bridge_hashmap, number_of_blocks, geometry, gap, *row)
+            {
+                return Err(conflict);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Random ladder of `geometry`, bridges of each gap are sorted by row
+pub fn calc_bridge_hashmap<R: Rng + ?Sized>(
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    rng: &mut R,
 ) -> HashMap<ChunkIndex, Vec<BridgeIndex>> {
-    let mut bridge_hashmap: HashMap<ChunkIndex, Vec<BridgeIndex>> = HashMap::new();
+    let number_of_gaps = number_of_blocks.saturating_sub(1) as ChunkIndex;
+    let mut bridge_hashmap: HashMap<ChunkIndex, Vec<BridgeIndex>> =
+        (0..number_of_gaps).map(|gap| (gap, Vec::new())).collect();
+    let counts = geometry.bridge_counts(number_of_gaps, rng);
 
-    for i in 0..(number_of_blocks - 1) {
-        let number_of_bridge: u8 = rng.gen_range(2, number_of_max_bridges);
+    for i in 0..number_of_gaps {
+        let number_of_bridge = counts[i as usize] as u8;
 
-        let vec_candidates = {
-            let index = if i == 0 { 0 } else { (i - 1) as u16 };
-            let range = 0..y_coordinate;
+        let vec_candidates = geometry
+            .bridge_rows()
+            .filter(|row| {
+                calc_bridge_conflict(&bridge_hashmap, number_of_blocks, geometry, i, *row).is_none()
+            })
+            .collect();
 
-            match bridge_hashmap.get(&index) {
-                Some(vec) => {
-                    let set: HashSet<&u16> = HashSet::from_iter(vec.iter());
-                    range.into_iter().filter(|x| !set.contains(x)).collect()
-                }
-                None => range.into_iter().collect(),
+        for row in calc_bridge_indexes(rng, number_of_bridge, vec_candidates) {
+            add_bridge(&mut bridge_hashmap, number_of_blocks, geometry, i, row)
+                .expect("candidate rows have no conflicting bridge");
+        }
+    }
+
+    bridge_hashmap
+}
+
+/// Random ladders generated before arranging one for the constraints
+const MAX_CONSTRAINED_ATTEMPTS: u32 = 2_000;
+/// Assignments tried to be arranged in the rows of the ladder
+const MAX_ARRANGED_ATTEMPTS: u32 = 1_000;
+
+/// Whether every name can get a different allowed result, where
+/// `allowed[name_index][result_index]`. Found by augmenting paths of
+/// bipartite matching.
+pub fn calc_has_assignment(allowed: &[Vec<bool>]) -> bool {
+    calc_assignment(allowed).is_some()
+}
+
+/// Random assignment where every name gets a different allowed result,
+/// `assignment[name_index]` is the result index
+pub fn calc_random_assignment<R: Rng + ?Sized>(
+    allowed: &[Vec<bool>],
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    // the same matching on shuffled names and results
+    let mut names: Vec<usize> = (0..allowed.len()).collect();
+    let mut results: Vec<usize> = (0..allowed.len()).collect();
+    names.shuffle(rng);
+    results.shuffle(rng);
+
+    let shuffled: Vec<Vec<bool>> = names
+        .iter()
+        .map(|name_index| {
+            results
+                .iter()
+                .map(|result_index| allowed[*name_index][*result_index])
+                .collect()
+        })
+        .collect();
+
+    let assignment = calc_assignment(&shuffled)?;
+    let mut unshuffled = vec![0; allowed.len()];
+    for (i, j) in assignment.into_iter().enumerate() {
+        unshuffled[names[i]] = results[j];
+    }
+
+    Some(unshuffled)
+}
+
+/// Assignment of `calc_has_assignment`, `None` if there is not any
+fn calc_assignment(allowed: &[Vec<bool>]) -> Option<Vec<usize>> {
+    fn augment(
+        name_index: usize,
+        allowed: &[Vec<bool>],
+        visited: &mut Vec<bool>,
+        owners: &mut Vec<Option<usize>>,
+    ) -> bool {
+        for result_index in 0..owners.len() {
+            if !allowed[name_index][result_index] || visited[result_index] {
+                continue;
             }
-        };
+            visited[result_index] = true;
+
+            let is_free = match owners[result_index] {
+                Some(owner) => augment(owner, allowed, visited, owners),
+                None => true,
+            };
+            if is_free {
+                owners[result_index] = Some(name_index);
+                return true;
+            }
+        }
 
-        let mut vec = calc_bridge_indexes(rng, number_of_bridge, vec_candidates);
-        vec.sort();
+        false
+    }
 
-        bridge_hashmap.insert(i.into(), vec);
+    let mut owners = vec![None; allowed.len()];
+    for name_index in 0..allowed.len() {
+        if !augment(
+            name_index,
+            allowed,
+            &mut vec![false; allowed.len()],
+            &mut owners,
+        ) {
+            return None;
+        }
     }
 
+    let mut assignment = vec![0; allowed.len()];
+    for (result_index, owner) in owners.into_iter().enumerate() {
+        assignment[owner?] = result_index;
+    }
+
+    Some(assignment)
+}
+
+/// Whether a bridge can be put in `row` of `gap`, with no bridge of the gap
+/// or of the neighbouring gaps in the row. The row is not shared even if
+/// `geometry.share_rows`, as a shared row would cross a pair of bridges.
+fn calc_is_free(
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    gap: ChunkIndex,
+    row: BridgeIndex,
+) -> bool {
+    let geometry = Geometry {
+        share_rows: false,
+        ..geometry.clone()
+    };
+
     bridge_hashmap
+        .get(&gap)
+        .map_or(true, |vec| !vec.contains(&row))
+        && calc_bridge_conflict(bridge_hashmap, number_of_blocks, &geometry, gap, row).is_none()
+}
+
+/// Random pair of free rows of `gap` with only free rows between them, so
+/// that the bridges cancel each other. `None` if there is not any.
+fn calc_free_pair<R: Rng + ?Sized>(
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    gap: ChunkIndex,
+    rng: &mut R,
+) -> Option<Vec<BridgeIndex>> {
+    // runs of free rows next to each other
+    let mut runs: Vec<Vec<BridgeIndex>> = Vec::new();
+    for row in geometry.bridge_rows() {
+        if !calc_is_free(bridge_hashmap, number_of_blocks, geometry, gap, row) {
+            continue;
+        }
+
+        match runs.last_mut() {
+            Some(run) if run.last() == Some(&(row - 1)) => run.push(row),
+            _ => runs.push(vec![row]),
+        }
+    }
+
+    let run = runs.iter().filter(|run| run.len() >= 2).choose(rng)?;
+    let mut pair: Vec<BridgeIndex> = run.choose_multiple(rng, 2).copied().collect();
+    pair.sort();
+
+    Some(pair)
+}
+
+/// Rows of swaps sorting `rails` where neighbouring gaps do not share a row.
+/// Swaps are picked in random order, or by odd-even transposition which needs
+/// a row per rail at most.
+fn calc_swap_rounds<R: Rng + ?Sized>(
+    mut rails: Vec<usize>,
+    rng: &mut R,
+    odd_even: bool,
+) -> Vec<Vec<ChunkIndex>> {
+    let number_of_gaps = rails.len().saturating_sub(1) as ChunkIndex;
+    let mut rounds: Vec<Vec<ChunkIndex>> = Vec::new();
+    let mut parity = 0;
+
+    while rails.windows(2).any(|pair| pair[0] > pair[1]) {
+        let gaps: Vec<ChunkIndex> = if odd_even {
+            parity = 1 - parity;
+            (1 - parity..number_of_gaps).step_by(2).collect()
+        } else {
+            let mut gaps: Vec<ChunkIndex> = (0..number_of_gaps).collect();
+            gaps.shuffle(rng);
+            gaps
+        };
+
+        // swaps of a round share a row, which neighbouring gaps cannot
+        let mut swaps: Vec<ChunkIndex> = Vec::new();
+        let mut round_hashmap: HashMap<ChunkIndex, Vec<BridgeIndex>> = HashMap::new();
+        for gap in gaps {
+            if rails[gap as usize] > rails[gap as usize + 1]
+                && calc_shared_neighbour(&round_hashmap, gap, 0).is_none()
+            {
+                swaps.push(gap);
+                round_hashmap.insert(gap, vec![0]);
+            }
+        }
+        for gap in swaps.iter() {
+            rails.swap(*gap as usize, *gap as usize + 1);
+        }
+
+        if !swaps.is_empty() {
+            rounds.push(swaps);
+        }
+    }
+
+    rounds
+}
+
+/// Ladder giving every name the result of `assignment`, `None` if it needs
+/// more rows than `geometry` has or a gap is left with fewer bridges than
+/// `geometry.min_bridges` or more than `geometry.max_bridges`. Neighbouring
+/// players in the wrong order are swapped, a round of swaps in a row, then
+/// pairs of bridges which cancel each other are added in the free rows.
+pub fn calc_arranged_bridge_hashmap<R: Rng + ?Sized>(
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    rng: &mut R,
+    assignment: &[usize],
+) -> Option<HashMap<ChunkIndex, Vec<BridgeIndex>>> {
+    let number_of_gaps = number_of_blocks.saturating_sub(1) as ChunkIndex;
+    let bridge_rows = geometry.bridge_rows();
+
+    // result index of the player on each rail
+    let mut rounds = calc_swap_rounds(assignment.to_vec(), rng, false);
+    if rounds.len() > bridge_rows.len() {
+        rounds = calc_swap_rounds(assignment.to_vec(), rng, true);
+    }
+    if rounds.len() > bridge_rows.len() {
+        return None;
+    }
+
+    let mut rows: Vec<BridgeIndex> = bridge_rows.clone().choose_multiple(rng, rounds.len());
+    rows.sort();
+
+    let mut bridge_hashmap: HashMap<ChunkIndex, Vec<BridgeIndex>> =
+        (0..number_of_gaps).map(|gap| (gap, Vec::new())).collect();
+    for (swaps, row) in rounds.iter().zip(rows) {
+        for gap in swaps {
+            add_bridge(&mut bridge_hashmap, number_of_blocks, geometry, *gap, row)
+                .expect("swaps of a round are not next to each other");
+        }
+    }
+
+    // pairs keep the parity of the swaps, one more or less bridge than the
+    // count is taken then
+    let min_bridges = geometry.min_bridges as usize;
+    let least_and_counts: Vec<(usize, usize)> = geometry
+        .bridge_counts(number_of_gaps, rng)
+        .into_iter()
+        .enumerate()
+        .map(|(gap, count)| {
+            let number_of_swaps = bridge_hashmap[&(gap as ChunkIndex)].len();
+            let least = min_bridges.max(number_of_swaps);
+            let least = least + (least - number_of_swaps) % 2;
+            let count = match count {
+                count if count <= least => least,
+                count if (count - number_of_swaps) % 2 == 0 => count,
+                count if count < geometry.max_bridges as usize => count + 1,
+                count => count - 1,
+            };
+
+            (least, count)
+        })
+        .collect();
+
+    // every gap gets its least bridges before the rows are taken by the rest
+    for is_least in [true, false].iter() {
+        for gap in 0..number_of_gaps {
+            let (least, count) = least_and_counts[gap as usize];
+            let number_of_bridge = if *is_least { least } else { count };
+
+            while bridge_hashmap[&gap].len() + 2 <= number_of_bridge {
+                let pair =
+                    match calc_free_pair(&bridge_hashmap, number_of_blocks, geometry, gap, rng) {
+                        Some(pair) => pair,
+                        None => break,
+                    };
+                for row in pair {
+                    add_bridge(&mut bridge_hashmap, number_of_blocks, geometry, gap, row)
+                        .expect("rows of the pair are free");
+                }
+            }
+        }
+    }
+
+    let counts = min_bridges..=geometry.max_bridges as usize;
+    if bridge_hashmap
+        .values()
+        .any(|vec| !counts.contains(&vec.len()))
+    {
+        return None;
+    }
+
+    Some(bridge_hashmap)
+}
+
+/// Generates ladders until every name gets one of its allowed results, so
+/// the ladder is still a genuine one, then arranges bridges for a random
+/// allowed assignment. `Err` if it is impossible or not found.
+pub fn calc_constrained_bridge_hashmap<R: Rng + ?Sized>(
+    number_of_blocks: u8,
+    geometry: &Geometry,
+    rng: &mut R,
+    allowed: &[Vec<bool>],
+) -> Result<HashMap<ChunkIndex, Vec<BridgeIndex>>, String> {
+    if !calc_has_assignment(allowed) {
+        return Err(String::from(
+            "no assignment satisfies the constraints, every player should have a result left",
+        ));
+    }
+
+    for _ in 0..MAX_CONSTRAINED_ATTEMPTS {
+        let bridge_hashmap = calc_bridge_hashmap(number_of_blocks, geometry, rng);
+        let path_hashmap = calc_path_hashmap(number_of_blocks, &bridge_hashmap, geometry.rows);
+
+        if calc_pairings(&path_hashmap)
+            .iter()
+            .all(|(name_index, result_index)| allowed[*name_index as usize][*result_index as usize])
+        {
+            return Ok(bridge_hashmap);
+        }
+    }
+
+    // random ladders rarely satisfy many constraints, e.g. pinned results
+    for _ in 0..MAX_ARRANGED_ATTEMPTS {
+        let bridge_hashmap = calc_random_assignment(allowed, rng).and_then(|assignment| {
+            calc_arranged_bridge_hashmap(number_of_blocks, geometry, rng, &assignment)
+        });
+
+        if let Some(bridge_hashmap) = bridge_hashmap {
+            return Ok(bridge_hashmap);
+        }
+    }
+
+    Err(format!(
+        "no ladder satisfying the constraints is found in {} rows",
+        geometry.rows
+    ))
 }
 
 pub fn calc_next_index(index: u8, limit: u8) -> u8 {
@@ -142,7 +946,7 @@ pub fn calc_bridge_points(
 
         vec![vec_1, vec_2]
             .into_iter()
-            .filter_map(|x| x)
+            .flatten()
             .flatten()
             .for_each(|x| {
                 vec.push(x);
@@ -156,10 +960,21 @@ pub fn calc_bridge_points(
     vec
 }
 
+/// Points where the path of `index` crosses bridges, and its end at `y_max`.
+/// In a row shared by bridges of neighbouring gaps, a player goes left by one
+/// bridge, or right along the whole line, so that players never meet.
 pub fn calc_path(index: u8, hashmap: &HashMap<u16, Vec<u16>>, y_max: u8) -> Vec<Point> {
     let mut curr_location = Point::new(index as i32, 0i32);
     let mut path = Vec::new();
 
+    // the other end of the bridge at row `y` of rail `x`, the left one first
+    let bridge_end = |x: i32, y: i32, right_only: bool| {
+        calc_bridge_points(x, hashmap)
+            .into_iter()
+            .find(|point| point.x == y && (!right_only || point.y > x))
+            .map(|point| point.y)
+    };
+
     loop {
         let Point { x, y } = curr_location;
         if y == y_max as i32 {
@@ -167,15 +982,21 @@ pub fn calc_path(index: u8, hashmap: &HashMap<u16, Vec<u16>>, y_max: u8) -> Vec<
             break;
         }
 
-        let vec_bridge_points = calc_bridge_points(x, hashmap);
-        let bridge_point = vec_bridge_points.iter().find(|point| point.x == y as i32);
-
-        match bridge_point {
-            Some(p) => {
+        match bridge_end(x, y, false) {
+            Some(end) => {
                 path.push(Point::new(x, y));
-                path.push(Point::new(p.y, y));
+                path.push(Point::new(end, y));
+
+                // a row shared by the next gaps is crossed to its right end
+                let mut x = end;
+                if end > curr_location.x {
+                    while let Some(end) = bridge_end(x, y, true) {
+                        x = end;
+                        path.push(Point::new(x, y));
+                    }
+                }
 
-                curr_location = Point::new(p.y, y + 1);
+                curr_location = Point::new(x, y + 1);
             }
             None => {
                 curr_location = Point::new(x, y + 1);
@@ -186,79 +1007,181 @@ pub fn calc_path(index: u8, hashmap: &HashMap<u16, Vec<u16>>, y_max: u8) -> Vec<
     path
 }
 
-pub fn calc_partial_line(
-    point_hashmap: &HashMap<Point, Point>,
-    path: &Vec<Point>,
-    tick: i32,
-    index: i32,
+pub fn calc_path_hashmap(
+    number_of_blocks: u8,
+    bridge_hashmap: &HashMap<ChunkIndex, Vec<BridgeIndex>>,
+    y_coordinate: u16,
+) -> HashMap<u8, Vec<Point>> {
+    (0..number_of_blocks)
+        .map(|index| {
+            let path = calc_path(index, bridge_hashmap, y_coordinate as u8);
+
+            (index, path)
+        })
+        .collect()
+}
+
+/// (name index, result index) of every player in the order of names, from
+/// the first and the last point of each path
+pub fn calc_pairings(path_hashmap: &HashMap<u8, Vec<Point>>) -> Vec<(u8, u8)> {
+    let mut pairings: Vec<(u8, u8)> = path_hashmap
+        .values()
+        .filter_map(|path| Some((path.first()?.x as u8, path.last()?.x as u8)))
+        .collect();
+    pairings.sort();
+
+    pairings
+}
+
+/// Index of the name whose path ends at `result_index`
+pub fn calc_name_index_of_result(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    result_index: u8,
+) -> Option<u8> {
+    calc_pairings(path_hashmap)
+        .into_iter()
+        .find(|(_, index)| *index == result_index)
+        .map(|(name_index, _)| name_index)
+}
+
+/// Terminal coordinates of `path` from the bottom of the name box to the top
+/// of the result box, or the other way round if `reverse`
+pub fn calc_path_points<'a>(
+    point_hashmap: &'a HashMap<Point, Point>,
+    path: &[Point],
     selected_chunk: u8,
-) -> (i32, Rect, LineDirection, i32) {
-    let start_point: (u16, i32) = if index == 0 {
-        (selected_chunk as u16, -1)
-    } else {
-        let Point { x, y } = path.get(index as usize - 1).unwrap();
+    reverse: bool,
+) -> Vec<&'a Point> {
+    let mut points: Vec<&Point> = Some(Point::new(selected_chunk as i32, -1))
+        .iter()
+        .chain(path.iter())
+        .map(|point| point_hashmap.get(point).unwrap())
+        .collect();
+    if reverse {
+        points.reverse();
+    }
 
-        (*x as u16, *y as i32)
-    };
-    let end_point = {
-        let Point { x, y } = path.get(index as usize).unwrap();
+    points
+}
 
-        (*x as u16, *y as i32)
+/// Number of cells between two points of a path segment, excluding both ends
+fn calc_segment_length(start_point: &Point, end_point: &Point) -> i32 {
+    let length = if start_point.x == end_point.x {
+        (end_point.y - start_point.y).abs()
+    } else {
+        (end_point.x - start_point.x).abs()
     };
 
-    let start_point = point_hashmap
-        .get(&Point::new(start_point.0 as i32, start_point.1))
-        .unwrap();
-    let end_point = point_hashmap
-        .get(&Point::new(end_point.0 as i32, end_point.1))
-        .unwrap();
+    (length - 1).max(0)
+}
 
-    let tuple = if start_point.x == end_point.x {
-        // direction down
-        let length = (end_point.y - start_point.y) as i32 - 1;
-        let length = min(tick, length);
+/// Ticks at which each segment of `path` is drawn to its end, from the
+/// result if `reverse`
+pub fn calc_segment_ends(
+    point_hashmap: &HashMap<Point, Point>,
+    path: &[Point],
+    selected_chunk: u8,
+    reverse: bool,
+) -> Vec<i32> {
+    calc_path_points(point_hashmap, path, selected_chunk, reverse)
+        .windows(2)
+        .scan(0, |tick, points| {
+            *tick += calc_segment_length(points[0], points[1]);
+
+            Some(*tick)
+        })
+        .collect()
+}
+
+/// Number of ticks needed to draw the whole `path`
+pub fn calc_path_length(
+    point_hashmap: &HashMap<Point, Point>,
+    path: &[Point],
+    selected_chunk: u8,
+) -> i32 {
+    calc_segment_ends(point_hashmap, path, selected_chunk, false)
+        .last()
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Number of ticks needed to draw `path` in the terminal `area`, `None` if
+/// the terminal is too small to draw the ladder. Widths of the boxes do not
+/// move the rails, so they are not needed here.
+pub fn calc_path_length_in_area(
+    area: Rect,
+    number_of_blocks: u8,
+    y_coordinate: u16,
+    path: &[Point],
+    selected_chunk: u8,
+) -> Option<i32> {
+    calc_sadari_layout(area, number_of_blocks, y_coordinate, 0)
+        .ok()
+        .map(|layout| calc_path_length(&layout.bridge_point_hashmap, path, selected_chunk))
+}
+
+/// Scales `tick` so that the animation keeps its progress after the path
+/// length is changed, e.g. by resizing the terminal
+pub fn calc_resized_tick(tick: i32, prev_path_length: i32, path_length: i32) -> i32 {
+    if tick > prev_path_length {
+        // animation was already finished
+        path_length + 1
+    } else if prev_path_length == 0 {
+        tick
+    } else {
+        (tick as i64 * path_length as i64 / prev_path_length as i64) as i32
+    }
+}
 
+/// Part of the segment from `start_point` to `end_point` drawn in `tick`,
+/// with the ticks left for the next segments
+pub fn calc_partial_line(
+    start_point: &Point,
+    end_point: &Point,
+    tick: i32,
+) -> (i32, Rect, LineDirection) {
+    let length = min(tick, calc_segment_length(start_point, end_point));
+    let left_tick = tick - length;
+
+    if start_point.x == end_point.x && start_point.y < end_point.y {
+        // direction down
         let area = Rect::new(
             start_point.x as u16,
             start_point.y as u16 + 1,
             2,
             length as u16,
         );
-        let left_tick = tick - length;
-        let next_index = if left_tick > 0 { index + 1 } else { index };
 
-        (left_tick, area, LineDirection::Down, next_index)
+        (left_tick, area, LineDirection::Down)
+    } else if start_point.x == end_point.x {
+        // direction up, tracing back from the result
+        let area = Rect::new(
+            start_point.x as u16,
+            start_point.y as u16 - length as u16,
+            2,
+            length as u16,
+        );
+
+        (left_tick, area, LineDirection::Up)
     } else if start_point.x < end_point.x {
         // direction right
-        let length = (end_point.x - start_point.x) as i32 - 1;
-        let length = min(tick, length);
-
         let area = Rect::new(
             start_point.x as u16 + 1,
             start_point.y as u16,
             length as u16,
             2,
         );
-        let left_tick = tick - length;
-        let next_index = if left_tick > 0 { index + 1 } else { index };
 
-        (left_tick, area, LineDirection::Right, next_index)
+        (left_tick, area, LineDirection::Right)
     } else {
         // direction left
-        let length = (start_point.x - end_point.x) as i32 - 1;
-        let length = min(tick, length);
-
         let area = Rect::new(
             start_point.x as u16 - length as u16,
             start_point.y as u16,
             length as u16,
             2,
         );
-        let left_tick = tick - length;
-        let next_index = if left_tick > 0 { index + 1 } else { index };
-
-        (left_tick, area, LineDirection::Left, next_index)
-    };
 
-    tuple
+        (left_tick, area, LineDirection::Left)
+    }
 }
@@ -0,0 +1,93 @@
+use crate::helper::{calc_pairings, calc_result_lines, markdown_cell, Point};
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, Write},
+};
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Format of the mapping copied to the clipboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopyFormat {
+    Plain,
+    Markdown,
+}
+
+impl CopyFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            CopyFormat::Plain => "plain text",
+            CopyFormat::Markdown => "Markdown",
+        }
+    }
+}
+
+/// Name -> result mapping in the order of names
+pub fn format_mapping(
+    format: CopyFormat,
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    result_vec: &[String],
+) -> String {
+    match format {
+        CopyFormat::Plain => calc_result_lines(path_hashmap, name_vec, result_vec).join("\n"),
+        CopyFormat::Markdown => {
+            let mut lines = vec![
+                String::from("| Name | Result |"),
+                String::from("| --- | --- |"),
+            ];
+            for (name_index, result_index) in calc_pairings(path_hashmap) {
+                lines.push(format!(
+                    "| {} | {} |",
+                    markdown_cell(&name_vec[name_index as usize]),
+                    markdown_cell(&result_vec[result_index as usize])
+                ));
+            }
+
+            lines.join("\n")
+        }
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// OSC 52 escape sequence setting the clipboard to `text`. Inside tmux, the
+/// sequence is wrapped so that tmux passes it to the outer terminal.
+pub fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+
+    if in_tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+/// Copies `text` to the system clipboard through the terminal, which works
+/// over SSH without any clipboard tool
+pub fn copy_to_clipboard<W: Write>(out: &mut W, text: &str) -> io::Result<()> {
+    let in_tmux = env::var_os("TMUX").is_some();
+
+    write!(out, "{}", osc52_sequence(text, in_tmux))?;
+    out.flush()
+}
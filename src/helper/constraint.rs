@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// Header of the constraints section in the input file
+pub const CONSTRAINTS_HEADER: &str = "[constraints]";
+
+/// Rule of the assignment between a name and a result
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignmentRule {
+    /// `name != result`, the name does not get the result
+    Forbid(String, String),
+    /// `name = result`, the name gets the result
+    Require(String, String),
+}
+
+impl AssignmentRule {
+    /// Reads `name != result` or `name = result`
+    pub fn parse(text: &str) -> Result<AssignmentRule, String> {
+        let split = |separator: &str| {
+            let mut split = text.splitn(2, separator);
+            match (split.next(), split.next()) {
+                (Some(name), Some(result)) if !name.trim().is_empty() => {
+                    Some((String::from(name.trim()), String::from(result.trim())))
+                }
+                _ => None,
+            }
+        };
+
+        if let Some((name, result)) = split("!=") {
+            Ok(AssignmentRule::Forbid(name, result))
+        } else if let Some((name, result)) = split("=") {
+            Ok(AssignmentRule::Require(name, result))
+        } else {
+            Err(format!(
+                "constraint should be `name != result` or `name = result`, got: {}",
+                text
+            ))
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        match self {
+            AssignmentRule::Forbid(name, _) | AssignmentRule::Require(name, _) => name,
+        }
+    }
+
+    pub fn result(&self) -> &String {
+        match self {
+            AssignmentRule::Forbid(_, result) | AssignmentRule::Require(_, result) => result,
+        }
+    }
+
+    /// Whether `name` may get `result` under this constraint
+    pub fn allows(&self, name: &str, result: &str) -> bool {
+        match self {
+            AssignmentRule::Forbid(a, b) => !(a == name && b == result),
+            AssignmentRule::Require(a, b) => a != name || b == result,
+        }
+    }
+}
+
+impl fmt::Display for AssignmentRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssignmentRule::Forbid(name, result) => write!(f, "{} != {}", name, result),
+            AssignmentRule::Require(name, result) => write!(f, "{} = {}", name, result),
+        }
+    }
+}
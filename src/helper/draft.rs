@@ -0,0 +1,72 @@
+use crate::helper::{calc_pairings, json_array, json_string, Point};
+use std::collections::HashMap;
+
+/// Results of the draft mode, positions from 1
+pub fn calc_positions(number_of_players: usize) -> Vec<String> {
+    (1..=number_of_players).map(|x| x.to_string()).collect()
+}
+
+/// Picks of the player drawing `position` (from 0), the snake second round
+/// goes in the reverse order
+pub fn calc_picks(position: usize, number_of_players: usize, snake: bool) -> Vec<usize> {
+    let mut picks = vec![position + 1];
+    if snake {
+        picks.push(2 * number_of_players - position);
+    }
+
+    picks
+}
+
+/// Names in the order of each draft round. Positions are the order of the
+/// results, so nobody shares a position.
+pub fn calc_draft_rounds(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    snake: bool,
+) -> Vec<Vec<String>> {
+    let mut pairings = calc_pairings(path_hashmap);
+    pairings.sort_by_key(|(_, result_index)| *result_index);
+
+    let order: Vec<String> = pairings
+        .iter()
+        .map(|(name_index, _)| name_vec[*name_index as usize].clone())
+        .collect();
+
+    let mut rounds = vec![order.clone()];
+    if snake {
+        rounds.push(order.into_iter().rev().collect());
+    }
+
+    rounds
+}
+
+/// One line per round with the pick numbers, e.g. `Round 2: 3. Bob, 4. Alice`
+pub fn format_draft(rounds: &[Vec<String>]) -> Vec<String> {
+    let mut pick = 0;
+
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(i, round)| {
+            let picks: Vec<String> = round
+                .iter()
+                .map(|name| {
+                    pick += 1;
+                    format!("{}. {}", pick, name)
+                })
+                .collect();
+
+            format!("Round {}: {}", i + 1, picks.join(", "))
+        })
+        .collect()
+}
+
+/// `{"draft":[["first",..],["last",..]]}`, one array of names per round
+pub fn format_draft_json(rounds: &[Vec<String>]) -> String {
+    let rounds_json = rounds
+        .iter()
+        .map(|round| json_array(round.iter().map(|name| json_string(name)).collect()))
+        .collect();
+
+    format!("{{\"draft\":{}}}", json_array(rounds_json))
+}
@@ -1,5 +1,8 @@
 use crate::helper;
-use crate::helper::SadariEnvironment;
+use crate::helper::{
+    Action, Animation, EditState, EditTarget, Keymap, ResultRow, ResultState, SadariEnvironment,
+    SetupColumn, SetupFocus, SetupState, Theme,
+};
 use std::{collections::HashMap, error::Error, fmt};
 use tui::{
     backend::Backend,
@@ -12,10 +15,45 @@ use tui::{
     Frame, Terminal,
 };
 
-fn create_simple_block<'a>(borders: Borders, color: Color) -> Block<'a> {
-    Block::default()
-        .borders(borders)
-        .border_style(Style::default().fg(color))
+fn create_simple_block<'a>(borders: Borders, style: Style) -> Block<'a> {
+    Block::default().borders(borders).border_style(style)
+}
+
+/// Areas of the result page
+#[derive(Debug)]
+pub struct ResultLayout {
+    pub title: Rect,
+    pub border: Rect,
+    /// columns of names, lines and results, one pairing in every two rows
+    pub columns: Vec<Rect>,
+}
+
+pub fn calc_result_layout(area: Rect) -> ResultLayout {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(5)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(area);
+
+    let inner = Block::default().borders(Borders::ALL).inner(chunks[1]);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .margin(2)
+        .constraints(
+            [
+                Constraint::Percentage(45),
+                Constraint::Length(20),
+                Constraint::Percentage(45),
+            ]
+            .as_ref(),
+        )
+        .split(inner);
+
+    ResultLayout {
+        title: chunks[0],
+        border: chunks[1],
+        columns,
+    }
 }
 
 #[derive(PartialEq)]
@@ -29,11 +67,13 @@ pub enum LineDirection {
     Left,
     Right,
     Down,
+    Up,
 }
 
 enum BorderKind {
     Selected,
     NotSelected,
+    Editing,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -55,10 +95,11 @@ impl fmt::Display for Point {
 }
 
 impl BorderKind {
-    fn color(self) -> Color {
+    fn style(self, theme: &Theme) -> Style {
         match self {
-            BorderKind::Selected => Color::Red,
-            BorderKind::NotSelected => Color::White,
+            BorderKind::Selected => theme.selected,
+            BorderKind::NotSelected => theme.border,
+            BorderKind::Editing => theme.editing,
         }
     }
 }
@@ -80,6 +121,13 @@ impl LineWidget {
 
 impl Widget for LineWidget {
     fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        // empty segments of the path, e.g. between rows next to each other,
+        // draw nothing
+        if area.area() == 0 || !area.intersects(*buf.area()) {
+            return;
+        }
+        let area = area.intersection(*buf.area());
+
         buf.set_string(area.left(), area.top(), self.line_type, Style::default());
 
         match self.line_type {
@@ -102,22 +150,60 @@ impl Widget for LineWidget {
     }
 }
 
-struct Label<'a> {
-    text: &'a str,
-    text_style: Style,
+/// Replaces box-drawing characters and arrows with ASCII ones, drawn over
+/// the whole screen at last in ASCII mode
+struct AsciiFilter;
+
+impl AsciiFilter {
+    fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+        let ascii = match symbol {
+            "│" | "┃" | "║" => "|",
+            "─" | "━" | "═" => "-",
+            "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "├" | "┤" | "┬" | "┴" | "┼" => {
+                "+"
+            }
+            "←" => "<",
+            "→" => ">",
+            "↑" => "^",
+            "↓" => "v",
+            "…" => ".",
+            _ => return None,
+        };
+
+        Some(ascii)
+    }
 }
 
-impl<'a> Default for Label<'a> {
-    fn default() -> Label<'a> {
-        Label {
-            text: "",
-            text_style: Style::default(),
+impl Widget for AsciiFilter {
+    fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        if !area.intersects(*buf.area()) {
+            return;
+        }
+        let area = area.intersection(*buf.area());
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some(symbol) = AsciiFilter::ascii_symbol(&cell.symbol) {
+                    cell.set_symbol(symbol);
+                }
+            }
         }
     }
 }
 
+#[derive(Default)]
+struct Label<'a> {
+    text: &'a str,
+    text_style: Style,
+}
+
 impl<'a> Widget for Label<'a> {
     fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        if !area.intersects(*buf.area()) {
+            return;
+        }
+
         buf.set_string(area.left(), area.top(), self.text, self.text_style);
     }
 }
@@ -139,7 +225,7 @@ pub fn _draw_bridge_point<B>(point_hashmap: &HashMap<Point, Point>, f: &mut Fram
 where
     B: Backend,
 {
-    for (_, value) in point_hashmap {
+    for value in point_hashmap.values() {
         let mut point = Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(Color::Red));
@@ -148,57 +234,80 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_sadari<B>(
     terminal: &mut Terminal<B>,
     sadari_env: &SadariEnvironment,
     selected_chunk: u8,
-    tick: i32,
+    animation: &Animation,
     rendering_state: &mut RenderingState,
     bridge_hashmap: &HashMap<u16, Vec<u16>>,
     path_hashmap: &HashMap<u8, Vec<Point>>,
+    edit_state: Option<&EditState>,
 ) -> Result<(), Box<dyn Error>>
 where
     B: Backend,
 {
     let number_of_blocks: u8 = sadari_env.number_of_blocks;
 
-    let y_coordinate = sadari_env.y_coordinate;
+    // text being edited is shown in place of the original one
+    let editing_text = |target: EditTarget, index: u8| -> Option<String> {
+        edit_state
+            .filter(|edit_state| edit_state.target == target && edit_state.index == index)
+            .map(|edit_state| format!("{}_", edit_state.text))
+    };
+
+    let y_coordinate = sadari_env.geometry.rows;
+    let theme = &sadari_env.theme;
     let name_vec = &sadari_env.name_vec;
     let result_vec = &sadari_env.result_vec;
 
     terminal.draw(|mut f| {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Percentage(15), // guide to user
-                    Constraint::Percentage(80), // main render
-                    Constraint::Percentage(5),
-                ]
-                .as_ref(),
-            )
-            .split(f.size());
+        let layout = match helper::calc_sadari_layout(
+            f.size(),
+            number_of_blocks,
+            y_coordinate,
+            sadari_env.text_width(),
+        ) {
+            Ok(layout) => layout,
+            Err(min_size) => {
+                draw_too_small(&mut f, min_size, theme, &sadari_env.keymap);
+                if sadari_env.ascii {
+                    f.render(&mut AsciiFilter, f.size());
+                }
+                return;
+            }
+        };
 
         // draw guide text
-        let guide_chunk = chunks[0];
         let guide_chunk = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)].as_ref())
             .horizontal_margin(10)
             .vertical_margin(1)
-            .split(guide_chunk);
-
-        let text = [Text::raw(
-            r#"
-←, → or h,l : Left, Right     s, enter : Start path animation
-q           : Quit            r        : Go to result        
-            "#,
-        )];
-
+            .split(layout.guide);
+
+        let text = [Text::raw(sadari_env.keymap.guide_text())];
+
+        let round = match sadari_env.keymap.keys(Action::NextRound).first() {
+            Some(key) if sadari_env.tournament => format!(
+                " Round {}, {}: next round",
+                sadari_env.round,
+                helper::key_name(*key)
+            ),
+            _ if sadari_env.tournament => format!(" Round {}", sadari_env.round),
+            _ => String::new(),
+        };
+        let title = format!(
+            "Rust-Sadari-Cli!{} (speed x{}{})",
+            round,
+            animation.speed() as f32 / 100.0,
+            if animation.paused { ", paused" } else { "" }
+        );
         let block = Block::default()
             .borders(Borders::NONE)
-            .title_style(Style::default().modifier(Modifier::BOLD).fg(Color::Green))
-            .title("Rust-Sadari-Cli!");
+            .title_style(theme.title)
+            .title(&title);
 
         let mut paragraph = Paragraph::new(text.iter())
             .block(block)
@@ -206,303 +315,686 @@ q           : Quit            r        : Go to result
         f.render(&mut paragraph, guide_chunk[0]);
 
         // draw footer
-        let footer_chunk = chunks[2];
         let footer_chunk = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)].as_ref())
             .horizontal_margin(10)
-            .split(footer_chunk);
+            .split(layout.footer);
 
+        let footer = "Github: 24seconds/rust-sadari-cli, powered by 24seconds";
         let text = [Text::styled(
-            "\n\n🍺 Github: 24seconds/rust-sadari-cli, powered by 24seconds",
-            Style::default().fg(Color::Yellow),
+            if sadari_env.ascii {
+                format!("\n\n{}", footer)
+            } else {
+                format!("\n\n🍺 {}", footer)
+            },
+            theme.footer,
         )];
         let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
         f.render(&mut paragraph, footer_chunk[0]);
 
-        // main chunk part
-        let main_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Percentage(10), // guide to user
-                    Constraint::Percentage(80), // main render
-                    Constraint::Percentage(10), // footer
-                ]
-                .as_ref(),
-            )
-            .horizontal_margin(10)
-            .split(chunks[1]);
-
-        let name_chunk = main_chunks[0];
-        let vec_names_layout = helper::calc_names_layout(number_of_blocks, 3, 1).unwrap();
-
         // render name_chunks
-        let name_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                vec_names_layout
-                    .iter()
-                    .map(|x| Constraint::Percentage(*x))
-                    .collect::<Vec<Constraint>>(),
-            )
-            .split(name_chunk);
-
         for i in 0..number_of_blocks {
+            let editing_name = editing_text(EditTarget::Name, i);
             let mut block = create_simple_block(
                 Borders::ALL,
                 match i {
-                    _ if i == selected_chunk => BorderKind::Selected.color(),
-                    _ => BorderKind::NotSelected.color(),
+                    _ if editing_name.is_some() => BorderKind::Editing.style(theme),
+                    _ if i == selected_chunk => BorderKind::Selected.style(theme),
+                    _ => BorderKind::NotSelected.style(theme),
                 },
             );
-            f.render(&mut block, name_chunks[i as usize * 2 + 1]);
+            f.render(&mut block, layout.name_rects[i as usize]);
 
             // draw name texts
-            let text = [Text::raw(name_vec.get(i as usize).unwrap())];
-            let mut paragraph = Paragraph::new(text.iter())
-                .alignment(Alignment::Center)
-                .wrap(true);
-            f.render(&mut paragraph, block.inner(name_chunks[i as usize * 2 + 1]));
+            let area = block.inner(layout.name_rects[i as usize]);
+            let text = [Text::raw(box_text(
+                editing_name,
+                name_vec.get(i as usize).unwrap(),
+                area.width,
+            ))];
+            let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
+            f.render(&mut paragraph, area);
         }
 
         // render result_chunks
-        let result_chunk = main_chunks[2];
-        let result_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                vec_names_layout
-                    .iter()
-                    .map(|x| Constraint::Percentage(*x))
-                    .collect::<Vec<Constraint>>(),
-            )
-            .split(result_chunk);
-
         for i in 0..number_of_blocks {
-            let mut block = create_simple_block(Borders::ALL, Color::White);
-            f.render(&mut block, result_chunks[i as usize * 2 + 1]);
+            let editing_result = editing_text(EditTarget::Result, i);
+            let mut block = create_simple_block(
+                Borders::ALL,
+                match i {
+                    _ if editing_result.is_some() => BorderKind::Editing.style(theme),
+                    _ => BorderKind::NotSelected.style(theme),
+                },
+            );
+            f.render(&mut block, layout.result_rects[i as usize]);
 
             // draw result texts
-            let text = [Text::raw(result_vec.get(i as usize).unwrap())];
-            let mut paragraph = Paragraph::new(text.iter())
-                .alignment(Alignment::Center)
-                .wrap(true);
-            f.render(
-                &mut paragraph,
-                block.inner(result_chunks[i as usize * 2 + 1]),
-            );
+            let area = block.inner(layout.result_rects[i as usize]);
+            let text = [Text::raw(box_text(
+                editing_result,
+                result_vec.get(i as usize).unwrap(),
+                area.width,
+            ))];
+            let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
+            f.render(&mut paragraph, area);
         }
 
-        let mut bridge_point_hashmap: HashMap<Point, Point> = HashMap::new();
-
         // render bridge vertical
-        let bridge_chunks: Vec<Rect> = name_chunks
-            .iter()
-            .zip(result_chunks.iter())
-            .map(|z| {
-                let n = z.0;
-                let r = z.1;
-                Rect::new(
-                    n.x + n.width / 2,
-                    n.y + n.height,
-                    n.width / 2,
-                    r.y - (n.y + n.height),
-                )
-            })
-            .collect();
-
-        for i in 0..number_of_blocks {
-            let mut line = create_simple_block(Borders::LEFT, Color::LightBlue);
-            f.render(&mut line, bridge_chunks[i as usize * 2 + 1]);
-
-            // collect bridge vertical points
-            let Rect {
-                x,
-                y,
-                width: _,
-                height,
-            } = bridge_chunks[i as usize * 2 + 1];
-
-            bridge_point_hashmap
-                .insert(Point::new(i as i32, -1), Point::new(x as i32, y as i32 - 1));
-            bridge_point_hashmap.insert(
-                Point::new(i as i32, y_coordinate as i32),
-                Point::new(x as i32, (y + height) as i32),
-            );
+        let mut line = LineWidget::new(theme.rail, line::VERTICAL);
+        for rail in &layout.rail_rects {
+            f.render(&mut line, *rail);
         }
-        // render bridge horizontal
-        for i in 0..(number_of_blocks - 1) {
-            let chunk_i = i as usize * 2 + 1;
-            let bridge_chunk = Rect::new(
-                bridge_chunks[chunk_i].x + 1,
-                bridge_chunks[chunk_i].y + 1,
-                bridge_chunks[chunk_i + 2].x - bridge_chunks[chunk_i].x - 1,
-                bridge_chunks[chunk_i].height - 2,
-            );
 
+        // render bridge horizontal
+        let mut line = LineWidget::new(theme.bridge, line::HORIZONTAL);
+        for (i, bridge_rects) in layout.bridge_rects.iter().enumerate() {
             let vec_indexes: &Vec<u16> = bridge_hashmap.get(&(i as u16)).unwrap();
-            let bridge_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(
-                    helper::calc_distributed_height(y_coordinate + 1, bridge_chunk.height)
-                        .iter()
-                        .map(|x| Constraint::Length(*x))
-                        .collect::<Vec<Constraint>>(),
-                )
-                .split(bridge_chunk);
 
-            let mut line = create_simple_block(Borders::BOTTOM, Color::Yellow);
             vec_indexes.iter().for_each(|vec_index| {
-                f.render(&mut line, bridge_chunks[*vec_index as usize]);
-
-                // collect bridge horizontal points
                 let Rect {
                     x,
                     y,
                     width,
                     height,
-                } = bridge_chunks[*vec_index as usize];
+                } = bridge_rects[*vec_index as usize];
 
-                bridge_point_hashmap.insert(
-                    Point::new(i as i32, *vec_index as i32),
-                    Point::new(x as i32 - 1, (y + height - 1) as i32),
-                );
-                bridge_point_hashmap.insert(
-                    Point::new(i as i32 + 1, *vec_index as i32),
-                    Point::new((x - 1 + width + 1) as i32, (y + height - 1) as i32),
-                );
+                f.render(&mut line, Rect::new(x, y + height - 1, width, 1));
             });
         }
 
         // draw animation
         let path = path_hashmap.get(&selected_chunk).unwrap();
 
-        let mut current_path_index = 0;
-        let mut left_tick = tick;
-        while left_tick > 0 && current_path_index < path.len() as usize {
-            let (tick, area, direction, next_path_index) = helper::calc_partial_line(
-                &bridge_point_hashmap,
-                &path,
-                left_tick,
-                current_path_index as i32,
-                selected_chunk,
-            );
+        // a click on the result traces the path back to the name
+        let points = helper::calc_path_points(
+            &layout.bridge_point_hashmap,
+            path,
+            selected_chunk,
+            animation.reverse,
+        );
+
+        let mut finished_segments = 0;
+        let mut left_tick = animation.tick;
+        for segment in points.windows(2) {
+            if left_tick <= 0 {
+                break;
+            }
 
+            let (tick, area, direction) =
+                helper::calc_partial_line(segment[0], segment[1], left_tick);
             left_tick = tick;
-            current_path_index = next_path_index as usize;
+            if left_tick > 0 {
+                finished_segments += 1;
+            }
 
             let mut line = LineWidget::new(
-                Style::default().fg(Color::Red),
+                theme.path,
                 match direction {
-                    LineDirection::Down => symbols::line::VERTICAL,
+                    LineDirection::Down | LineDirection::Up => symbols::line::VERTICAL,
                     LineDirection::Right | LineDirection::Left => symbols::line::HORIZONTAL,
                 },
             );
 
             f.render(&mut line, area);
         }
+        let is_drawn = finished_segments == path.len();
 
-        if current_path_index == path.len() {
-            // result chunk border should be red
+        if is_drawn {
+            // result chunk border should be highlighted
             let Point {
                 x: result_index,
                 y: _,
             } = path.last().unwrap();
 
-            let mut block = create_simple_block(Borders::ALL, Color::Red);
-            f.render(&mut block, result_chunks[*result_index as usize * 2 + 1]);
+            let mut block = create_simple_block(Borders::ALL, theme.result);
+            f.render(&mut block, layout.result_rects[*result_index as usize]);
 
             *rendering_state = RenderingState::Done;
         }
+
+        // full texts of the selected name and its result when they are cut in the boxes
+        let name = name_vec.get(selected_chunk as usize).unwrap();
+        let result = Some(path.last().unwrap())
+            .filter(|_| is_drawn)
+            .map(|point| (point.x, result_vec.get(point.x as usize).unwrap()));
+        let box_width = |rect: &Rect| rect.width.saturating_sub(2);
+        let is_truncated = helper::calc_text_width(name)
+            > box_width(&layout.name_rects[selected_chunk as usize])
+            || result.is_some_and(|(index, result)| {
+                helper::calc_text_width(result) > box_width(&layout.result_rects[index as usize])
+            });
+
+        // the full texts take the row of the avoidance note
+        let footer_line = if is_truncated && edit_state.is_none() {
+            let detail = match result {
+                Some((_, result)) => format!("{} → {}", name, result),
+                None => name.to_string(),
+            };
+            Some((detail, theme.selected))
+        } else {
+            sadari_env
+                .avoidance_note
+                .as_ref()
+                .map(|note| (note.to_string(), theme.warning))
+        };
+
+        if let Some((line, style)) = footer_line {
+            let text = [Text::styled(
+                helper::calc_truncated_text(&line, layout.footer.width),
+                style,
+            )];
+            let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
+            f.render(
+                &mut paragraph,
+                Rect::new(layout.footer.x, layout.footer.y, layout.footer.width, 1),
+            );
+        }
+
+        if sadari_env.ascii {
+            f.render(&mut AsciiFilter, f.size());
+        }
     })?;
 
     Ok(())
 }
 
+/// Text of a name or result box cut to the box `width`. Text being edited
+/// keeps its end, where the cursor is.
+fn box_text(editing_text: Option<String>, text: &str, width: u16) -> String {
+    match editing_text {
+        Some(editing_text) => helper::calc_truncated_text_end(&editing_text, width),
+        None => helper::calc_truncated_text(text, width),
+    }
+}
+
+/// `text` after the keys of `actions` in a status line, e.g. `o: order by name`.
+/// Actions without a key are left out.
+fn key_hint(keymap: &Keymap, actions: &[Action], text: &str) -> String {
+    let names: Vec<String> = actions
+        .iter()
+        .map(|action| keymap.key_names(*action))
+        .filter(|names| !names.is_empty())
+        .collect();
+
+    match names.is_empty() {
+        true => String::from(text),
+        false => format!("{}: {}", names.join(", "), text),
+    }
+}
+
+fn draw_too_small<B>(f: &mut Frame<B>, min_size: (u16, u16), theme: &Theme, keymap: &Keymap)
+where
+    B: Backend,
+{
+    let size = f.size();
+    let text = [
+        Text::styled(
+            "Terminal is too small!\n\n",
+            theme
+                .warning
+                .modifier(theme.warning.modifier | Modifier::BOLD),
+        ),
+        Text::raw(format!(
+            "current: {} x {}\nrequired: {} x {}\n\n{} : Quit",
+            size.width,
+            size.height,
+            min_size.0,
+            min_size.1,
+            keymap.key_names(Action::Quit)
+        )),
+    ];
+
+    let area = Rect::new(
+        size.x,
+        size.y + size.height.saturating_sub(6) / 2,
+        size.width,
+        size.height.min(6),
+    );
+    let mut paragraph = Paragraph::new(text.iter())
+        .alignment(Alignment::Center)
+        .wrap(true);
+    f.render(&mut paragraph, area);
+}
+
 pub fn render_result<B>(
     terminal: &mut Terminal<B>,
     sadari_env: &SadariEnvironment,
     path_hashmap: &HashMap<u8, Vec<Point>>,
+    result_state: &mut ResultState,
 ) -> Result<(), Box<dyn Error>>
 where
     B: Backend,
 {
+    if sadari_env.teams.is_some() {
+        return render_teams(terminal, sadari_env, path_hashmap, result_state);
+    }
+    if sadari_env.winner().is_some() {
+        return render_bracket(terminal, sadari_env);
+    }
+
+    let theme = &sadari_env.theme;
+    let rows = result_state.rows(sadari_env, path_hashmap);
+
     terminal.draw(|mut f| {
-        let size = f.size();
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(5)
-            .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
-            .split(size);
+        let layout = calc_result_layout(f.size());
+
+        // one pairing in every two lines, the last one does not need the blank line
+        let page_rows = (layout.columns[0].height as usize).div_ceil(2);
+        result_state.clamp_scroll(rows.len(), page_rows);
+        let visible_rows =
+            &rows[result_state.scroll..(result_state.scroll + page_rows).min(rows.len())];
+
+        let row_style = |row: &ResultRow| match result_state.highlighted {
+            Some(name_index) if name_index == row.name_index => theme.highlight,
+            _ => Style::default(),
+        };
 
-        let length = path_hashmap.len() as u8;
+        let mut block = create_simple_block(Borders::ALL, theme.border);
+        f.render(&mut block, layout.border);
 
-        let vec_text: Vec<(&String, &String)> = (0..length)
-            .into_iter()
-            .map(|i| {
-                let path = path_hashmap.get(&i).unwrap();
-                let start = path.first().unwrap();
-                let end = path.last().unwrap();
+        let mut label = Label::default()
+            .text("Sadari Result")
+            .text_style(theme.title);
+        f.render(&mut label, layout.title);
+
+        // order, groups, search and scroll position with their keys
+        let keymap = &sadari_env.keymap;
+        let search = if result_state.searching {
+            format!("/{}_", result_state.query)
+        } else if result_state.query.is_empty() {
+            key_hint(keymap, &[Action::Search], "search")
+        } else {
+            format!("/{}", result_state.query)
+        };
+        let position = match visible_rows.len() {
+            0 => format!("no pairing of {}", path_hashmap.len()),
+            len => format!(
+                "{}-{} of {}",
+                result_state.scroll + 1,
+                result_state.scroll + len,
+                rows.len()
+            ),
+        };
+        let status = match &result_state.notice {
+            Some(notice) => notice.to_string(),
+            None => [
+                key_hint(
+                    keymap,
+                    &[Action::Order],
+                    &format!("order by {}", result_state.order.name()),
+                ),
+                key_hint(
+                    keymap,
+                    &[Action::Group],
+                    &format!("groups {}", if result_state.grouped { "on" } else { "off" }),
+                ),
+                search,
+                key_hint(keymap, &[Action::ScrollUp, Action::ScrollDown], &position),
+                key_hint(keymap, &[Action::Copy, Action::CopyMarkdown], "copy"),
+            ]
+            .join("   "),
+        };
+        let mut label = Label::default()
+            .text(&status)
+            .text_style(if result_state.searching {
+                theme.editing
+            } else {
+                theme.footer
+            });
+        f.render(
+            &mut label,
+            Rect::new(
+                layout.title.x,
+                layout.title.y + 1,
+                layout.title.width,
+                layout.title.height.saturating_sub(1),
+            ),
+        );
+
+        let vec_start_text: Vec<Text> = visible_rows
+            .iter()
+            .map(|row| {
+                let start = &sadari_env.name_vec[row.name_index as usize];
 
-                let start = start.x;
-                let end = end.x;
+                let start = helper::calc_truncated_text(start, layout.columns[0].width);
+                Text::styled(format!("{}\n\n", start), row_style(row))
+            })
+            .collect();
+        let mut paragraph = Paragraph::new(vec_start_text.iter()).alignment(Alignment::Right);
+        f.render(&mut paragraph, layout.columns[0]);
 
-                let start = sadari_env.name_vec.get(start as usize).unwrap();
-                let end = sadari_env.result_vec.get(end as usize).unwrap();
+        let vec_line: Vec<Text> = visible_rows
+            .iter()
+            .map(|row| Text::styled("<───────────>\n\n", row_style(row)))
+            .collect();
+        let mut paragraph = Paragraph::new(vec_line.iter()).alignment(Alignment::Center);
+        f.render(&mut paragraph, layout.columns[1]);
 
-                (start, end)
+        let vec_end_text: Vec<Text> = visible_rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let end = &sadari_env.result_vec[row.result_index as usize];
+                // pick of the snake round next to the drawn position
+                let end = if sadari_env.snake {
+                    let picks: Vec<String> = helper::calc_picks(
+                        row.result_index as usize,
+                        sadari_env.number_of_blocks as usize,
+                        true,
+                    )
+                    .iter()
+                    .map(|pick| pick.to_string())
+                    .collect();
+                    format!("{} (picks {})", end, picks.join(", "))
+                } else {
+                    end.to_string()
+                };
+
+                // grouped results are shown once on the first row of each group,
+                // or on the first visible row when the group is scrolled
+                let end = match row.group_size {
+                    Some(size) if size > 1 => format!("{} ({})", end, size),
+                    Some(_) => end,
+                    None if result_state.grouped && i > 0 => String::new(),
+                    None => end,
+                };
+
+                let end = helper::calc_truncated_text(&end, layout.columns[2].width);
+                Text::styled(format!("{}\n\n", end), row_style(row))
             })
             .collect();
+        let mut paragraph = Paragraph::new(vec_end_text.iter()).alignment(Alignment::Left);
+        f.render(&mut paragraph, layout.columns[2]);
 
-        let mut block = create_simple_block(Borders::ALL, Color::White);
-        f.render(&mut block, chunks[1]);
+        if sadari_env.ascii {
+            f.render(&mut AsciiFilter, f.size());
+        }
+    })?;
 
-        let main_chunks = Layout::default()
-            .direction(Direction::Horizontal)
+    Ok(())
+}
+
+/// Result page of the finished tournament, every round and the winner
+fn render_bracket<B>(
+    terminal: &mut Terminal<B>,
+    sadari_env: &SadariEnvironment,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+{
+    let theme = &sadari_env.theme;
+    let lines = helper::format_bracket(&sadari_env.rounds);
+
+    terminal.draw(|mut f| {
+        let layout = calc_result_layout(f.size());
+
+        let mut block = create_simple_block(Borders::ALL, theme.border);
+        f.render(&mut block, layout.border);
+
+        let mut label = Label::default()
+            .text("Sadari Tournament")
+            .text_style(theme.title);
+        f.render(&mut label, layout.title);
+
+        let inner = Block::default().borders(Borders::ALL).inner(layout.border);
+        let inner = Layout::default()
+            .constraints([Constraint::Min(0)].as_ref())
+            .margin(1)
+            .split(inner)[0];
+        let vec_line_text: Vec<Text> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = helper::calc_truncated_text(line, inner.width);
+                // the winner is on the last line
+                if i + 1 == lines.len() {
+                    Text::styled(format!("\n{}\n", line), theme.selected)
+                } else {
+                    Text::raw(format!("{}\n", line))
+                }
+            })
+            .collect();
+        let mut paragraph = Paragraph::new(vec_line_text.iter()).alignment(Alignment::Center);
+        f.render(&mut paragraph, inner);
+
+        if sadari_env.ascii {
+            f.render(&mut AsciiFilter, f.size());
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Result page of the team mode, a box of members for every team
+fn render_teams<B>(
+    terminal: &mut Terminal<B>,
+    sadari_env: &SadariEnvironment,
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    result_state: &ResultState,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+{
+    let theme = &sadari_env.theme;
+    let teams = helper::calc_teams(path_hashmap, &sadari_env.name_vec, &sadari_env.result_vec);
+
+    terminal.draw(|mut f| {
+        let layout = calc_result_layout(f.size());
+
+        let mut block = create_simple_block(Borders::ALL, theme.border);
+        f.render(&mut block, layout.border);
+
+        let mut label = Label::default()
+            .text("Sadari Teams")
+            .text_style(theme.title);
+        f.render(&mut label, layout.title);
+
+        let status = match &result_state.notice {
+            Some(notice) => notice.to_string(),
+            None => format!(
+                "{} teams   {}",
+                teams.len(),
+                key_hint(
+                    &sadari_env.keymap,
+                    &[Action::Copy, Action::CopyMarkdown],
+                    "copy"
+                )
+            ),
+        };
+        let mut label = Label::default().text(&status).text_style(theme.footer);
+        f.render(
+            &mut label,
+            Rect::new(
+                layout.title.x,
+                layout.title.y + 1,
+                layout.title.width,
+                layout.title.height.saturating_sub(1),
+            ),
+        );
+
+        // up to 4 teams in a line
+        let number_of_columns = teams.len().clamp(1, 4);
+        let number_of_lines = teams.len().div_ceil(number_of_columns);
+        let inner = Block::default().borders(Borders::ALL).inner(layout.border);
+        let lines = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(vec![
+                Constraint::Ratio(1, number_of_lines as u32);
+                number_of_lines
+            ])
+            .split(inner);
+
+        for (line, teams) in lines.iter().zip(teams.chunks(number_of_columns)) {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![
+                    Constraint::Ratio(1, number_of_columns as u32);
+                    number_of_columns
+                ])
+                .split(*line);
+
+            for (cell, (team, members)) in cells.iter().zip(teams) {
+                let title = helper::calc_truncated_text(
+                    &format!("{} ({})", team, members.len()),
+                    cell.width.saturating_sub(2),
+                );
+                let mut block = create_simple_block(Borders::ALL, theme.border)
+                    .title_style(theme.title)
+                    .title(&title);
+                f.render(&mut block, *cell);
+
+                let inner = block.inner(*cell);
+                let vec_member_text: Vec<Text> = members
+                    .iter()
+                    .map(|member| {
+                        let member = helper::calc_truncated_text(member, inner.width);
+                        Text::raw(format!("{}\n", member))
+                    })
+                    .collect();
+                let mut paragraph =
+                    Paragraph::new(vec_member_text.iter()).alignment(Alignment::Center);
+                f.render(&mut paragraph, inner);
+            }
+        }
+
+        if sadari_env.ascii {
+            f.render(&mut AsciiFilter, f.size());
+        }
+    })?;
+
+    Ok(())
+}
+
+pub fn render_setup<B>(
+    terminal: &mut Terminal<B>,
+    setup_state: &SetupState,
+    sadari_env: &SadariEnvironment,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+{
+    let theme = &sadari_env.theme;
+    let validation = setup_state.validate();
+
+    terminal.draw(|mut f| {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
             .margin(2)
             .constraints(
                 [
-                    Constraint::Percentage(45),
-                    Constraint::Length(20),
-                    Constraint::Percentage(45),
+                    Constraint::Length(6), // guide to user
+                    Constraint::Min(0),    // names and results
+                    Constraint::Length(2), // validation message
+                    Constraint::Length(3), // start button
                 ]
                 .as_ref(),
             )
-            .split(block.inner(chunks[1]));
+            .split(f.size());
 
-        let mut label = Label::default()
-            .text("Sadari Result")
-            .text_style(Style::default().modifier(Modifier::BOLD).fg(Color::Green));
-        f.render(&mut label, chunks[0]);
+        // draw guide text, keys of the setup screen are fixed as the keymap
+        // binds the keys of the ladder and the result page
+        let text = [Text::raw(
+            r#"
+↑, ↓      : Move row              tab    : Name / Result         
+enter     : Add row               ctrl+x : Remove row            
+ctrl+u, d : Move field up, down   ctrl+r : Auto generated results
+ctrl+s    : Start                 esc    : Quit                  
+            "#,
+        )];
 
-        let vec_start_text: Vec<Text> = vec_text
-            .iter()
-            .map(|x| {
-                let (start, _) = *x;
+        let block = Block::default()
+            .borders(Borders::NONE)
+            .title_style(theme.title)
+            .title("Rust-Sadari-Cli! Setup");
 
-                Text::raw(format!("{}\n\n", start))
-            })
-            .collect();
-        let mut paragraph = Paragraph::new(vec_start_text.iter()).alignment(Alignment::Right);
-        f.render(&mut paragraph, main_chunks[0]);
+        let mut paragraph = Paragraph::new(text.iter())
+            .block(block)
+            .alignment(Alignment::Center);
+        f.render(&mut paragraph, chunks[0]);
 
-        let vec_line: Vec<Text> = (0..length)
-            .into_iter()
-            .map(|_| Text::raw("<───────────>\n\n"))
-            .collect();
-        let mut paragraph = Paragraph::new(vec_line.iter()).alignment(Alignment::Center);
-        f.render(&mut paragraph, main_chunks[1]);
+        // draw names and results
+        let field_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(chunks[1]);
 
-        let vec_end_text: Vec<Text> = vec_text
-            .iter()
-            .map(|x| {
-                let (_, end) = *x;
+        [
+            (SetupColumn::Name, "Names", &setup_state.name_vec),
+            (SetupColumn::Result, "Results", &setup_state.result_vec),
+        ]
+        .iter()
+        .zip(field_chunks.iter())
+        .for_each(|((column, title, vec), area)| {
+            let mut block = create_simple_block(Borders::ALL, theme.border).title(title);
+            f.render(&mut block, *area);
+
+            let vec_text: Vec<Text> = vec
+                .iter()
+                .enumerate()
+                .map(|(row, field)| {
+                    if setup_state.focus == SetupFocus::Field(row, *column) {
+                        Text::styled(
+                            format!("{:>2}. {}_\n", row + 1, field),
+                            BorderKind::Selected.style(theme),
+                        )
+                    } else {
+                        Text::raw(format!("{:>2}. {}\n", row + 1, field))
+                    }
+                })
+                .collect();
+            let mut paragraph = Paragraph::new(vec_text.iter());
+            f.render(&mut paragraph, block.inner(*area));
+        });
+
+        // draw validation message
+        let text = match &validation {
+            Ok(()) => [Text::styled(
+                format!("Ready! {} players", setup_state.number_of_rows()),
+                theme.ok,
+            )],
+            Err(message) => [Text::styled(message.to_string(), theme.warning)],
+        };
+        let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
+        f.render(&mut paragraph, chunks[2]);
 
-                Text::raw(format!("{}\n\n", end))
-            })
-            .collect();
-        let mut paragraph = Paragraph::new(vec_end_text.iter()).alignment(Alignment::Left);
-        f.render(&mut paragraph, main_chunks[2]);
+        // draw start button
+        let button_chunk = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(40),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[3])[1];
+
+        let mut block = create_simple_block(
+            Borders::ALL,
+            match setup_state.focus {
+                SetupFocus::Start => BorderKind::Selected.style(theme),
+                _ => BorderKind::NotSelected.style(theme),
+            },
+        );
+        f.render(&mut block, button_chunk);
+
+        let text = [Text::styled(
+            "Start",
+            if validation.is_ok() {
+                theme.ok
+            } else {
+                theme.disabled
+            },
+        )];
+        let mut paragraph = Paragraph::new(text.iter()).alignment(Alignment::Center);
+        f.render(&mut paragraph, block.inner(button_chunk));
+
+        if sadari_env.ascii {
+            f.render(&mut AsciiFilter, f.size());
+        }
     })?;
 
     Ok(())
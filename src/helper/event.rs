@@ -1,71 +1,259 @@
-use std::io;
 use std::sync::mpsc;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
-};
+use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use termion::event::Key;
-use termion::input::TermRead;
+/// Key input, independent from the terminal backend. Enter and Tab are
+/// `Char('\n')` and `Char('\t')`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Backspace,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    BackTab,
+    Delete,
+    Insert,
+    F(u8),
+    Char(char),
+    Alt(char),
+    Ctrl(char),
+    Null,
+    Esc,
+}
+
+#[cfg(all(feature = "termion", not(feature = "crossterm")))]
+impl From<termion::event::Key> for Key {
+    fn from(key: termion::event::Key) -> Key {
+        use termion::event::Key as TKey;
+
+        match key {
+            TKey::Backspace => Key::Backspace,
+            TKey::Left => Key::Left,
+            TKey::Right => Key::Right,
+            TKey::Up => Key::Up,
+            TKey::Down => Key::Down,
+            TKey::Home => Key::Home,
+            TKey::End => Key::End,
+            TKey::PageUp => Key::PageUp,
+            TKey::PageDown => Key::PageDown,
+            TKey::BackTab => Key::BackTab,
+            TKey::Delete => Key::Delete,
+            TKey::Insert => Key::Insert,
+            TKey::F(n) => Key::F(n),
+            TKey::Char(c) => Key::Char(c),
+            TKey::Alt(c) => Key::Alt(c),
+            TKey::Ctrl(c) => Key::Ctrl(c),
+            TKey::Esc => Key::Esc,
+            _ => Key::Null,
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<crossterm::event::KeyEvent> for Key {
+    fn from(key: crossterm::event::KeyEvent) -> Key {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        match key.code {
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Ctrl(c),
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) => Key::Alt(c),
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::Enter => Key::Char('\n'),
+            KeyCode::Tab => Key::Char('\t'),
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Home => Key::Home,
+            KeyCode::End => Key::End,
+            KeyCode::PageUp => Key::PageUp,
+            KeyCode::PageDown => Key::PageDown,
+            KeyCode::BackTab => Key::BackTab,
+            KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
+            KeyCode::F(n) => Key::F(n),
+            KeyCode::Esc => Key::Esc,
+            KeyCode::Null => Key::Null,
+        }
+    }
+}
+
+/// Mouse input, independent from the terminal backend. Coordinates are
+/// 0-based column and row as in `tui::layout::Rect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mouse {
+    /// Left button pressed
+    Press(u16, u16),
+    ScrollUp(u16, u16),
+    ScrollDown(u16, u16),
+}
+
+/// Reads keys and mouse events from the terminal and passes them to `handle`
+/// until it returns `false` or the input is closed
+#[cfg(all(feature = "termion", not(feature = "crossterm")))]
+fn read_input<F>(mut handle: F)
+where
+    F: FnMut(Event<Key>) -> bool,
+{
+    use termion::event::{Event as TEvent, MouseButton, MouseEvent};
+    use termion::input::TermRead;
+
+    for event in std::io::stdin().events().flatten() {
+        // termion coordinates start from 1
+        let event = match event {
+            TEvent::Key(key) => Event::Input(Key::from(key)),
+            TEvent::Mouse(MouseEvent::Press(button, x, y)) => {
+                let (x, y) = (x.saturating_sub(1), y.saturating_sub(1));
+                match button {
+                    MouseButton::Left => Event::Mouse(Mouse::Press(x, y)),
+                    MouseButton::WheelUp => Event::Mouse(Mouse::ScrollUp(x, y)),
+                    MouseButton::WheelDown => Event::Mouse(Mouse::ScrollDown(x, y)),
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        if !handle(event) {
+            return;
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+fn read_input<F>(mut handle: F)
+where
+    F: FnMut(Event<Key>) -> bool,
+{
+    use crossterm::event::{self, Event as CEvent, MouseButton, MouseEvent};
+
+    while let Ok(event) = event::read() {
+        let event = match event {
+            CEvent::Key(key) => Event::Input(Key::from(key)),
+            CEvent::Mouse(MouseEvent::Down(MouseButton::Left, x, y, _)) => {
+                Event::Mouse(Mouse::Press(x, y))
+            }
+            CEvent::Mouse(MouseEvent::ScrollUp(x, y, _)) => Event::Mouse(Mouse::ScrollUp(x, y)),
+            CEvent::Mouse(MouseEvent::ScrollDown(x, y, _)) => Event::Mouse(Mouse::ScrollDown(x, y)),
+            _ => continue,
+        };
+
+        if !handle(event) {
+            return;
+        }
+    }
+}
+
+/// Receives the first of `exit_keys` read from the terminal, e.g. to stop a
+/// script played instead of the terminal input
+pub fn read_exit_key(exit_keys: Vec<Key>) -> mpsc::Receiver<Key> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        #[cfg(any(feature = "termion", feature = "crossterm"))]
+        read_input(|event| match event {
+            Event::Input(key) if exit_keys.contains(&key) => {
+                let _ = tx.send(key);
+                false
+            }
+            _ => true,
+        })
+    });
+
+    rx
+}
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Event<I> {
     Input(I),
+    Mouse(Mouse),
     Tick,
+    /// Animation frame, sent only while frames are enabled
+    Frame,
 }
 
-/// A small event handler that wrap termion input and tick events. Each event
-/// type is handled in its own thread and returned to a common `Receiver`
+/// Events driving the app, read from the terminal by `Events` or from a
+/// script by `ScriptedEvents`
+pub trait EventSource {
+    /// Next event, `None` if there is no more input
+    fn next(&mut self) -> Option<Event<Key>>;
+
+    fn enable_frame(&mut self) {}
+
+    fn disable_frame(&mut self) {}
+
+    /// Time of the last event, for the speed of the animation
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Whether frames are sent, `None` once `Events` is dropped
+type FrameSwitch = Arc<(Mutex<Option<bool>>, Condvar)>;
+
+/// A small event handler that wrap terminal input, mouse and tick events. Each event
+/// type is handled in its own thread and returned to a common `Receiver`.
+/// The input thread reads every key until `Events` is dropped, so that only
+/// the app decides which key quits, e.g. not while typing a name.
 pub struct Events {
     rx: mpsc::Receiver<Event<Key>>,
     _input_handle: thread::JoinHandle<()>,
-    ignore_exit_key: Arc<AtomicBool>,
     _tick_handle: thread::JoinHandle<()>,
+    send_frame: FrameSwitch,
+    _frame_handle: thread::JoinHandle<()>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Config {
-    pub exit_key: Key,
     pub tick_rate: Duration,
+    pub frame_rate: Duration,
 }
 
 impl Default for Config {
     fn default() -> Config {
         Config {
-            exit_key: Key::Char('q'),
             tick_rate: Duration::from_millis(250),
+            frame_rate: Duration::from_millis(1000 / 60),
         }
     }
 }
 
+impl Default for Events {
+    fn default() -> Events {
+        Events::new()
+    }
+}
+
 impl Events {
     #[allow(dead_code)]
     pub fn new() -> Events {
         Events::with_config(Config::default())
     }
 
+    fn switch_frame(&self, state: Option<bool>) {
+        let (lock, condvar) = &*self.send_frame;
+        *lock.lock().unwrap() = state;
+        condvar.notify_one();
+    }
+
     pub fn with_config(config: Config) -> Events {
         let (tx, rx) = mpsc::channel();
-        let ignore_exit_key = Arc::new(AtomicBool::new(false));
+        let Config {
+            tick_rate,
+            frame_rate,
+        } = config;
         let input_handle = {
             let tx = tx.clone();
-            let ignore_exit_key = ignore_exit_key.clone();
             thread::spawn(move || {
-                let stdin = io::stdin();
-                for evt in stdin.keys() {
-                    match evt {
-                        Ok(key) => {
-                            if let Err(_) = tx.send(Event::Input(key)) {
-                                return;
-                            }
-                            if !ignore_exit_key.load(Ordering::Relaxed) && key == config.exit_key {
-                                return;
-                            }
-                        }
-                        Err(_) => {}
-                    }
-                }
+                // only the missing backend is reported without one
+                #[cfg(any(feature = "termion", feature = "crossterm"))]
+                read_input(|event| tx.send(event).is_ok())
             })
         };
         let tick_handle = {
@@ -73,30 +261,64 @@ impl Events {
             thread::spawn(move || {
                 let tx = tx.clone();
                 loop {
-                    tx.send(Event::Tick).unwrap();
-                    thread::sleep(config.tick_rate);
+                    if tx.send(Event::Tick).is_err() {
+                        return;
+                    }
+                    thread::sleep(tick_rate);
+                }
+            })
+        };
+        let send_frame: FrameSwitch = Arc::new((Mutex::new(Some(false)), Condvar::new()));
+        let frame_handle = {
+            let tx = tx.clone();
+            let send_frame = send_frame.clone();
+            thread::spawn(move || {
+                let (lock, condvar) = &*send_frame;
+                loop {
+                    // sleeps until frames are enabled instead of polling
+                    let mut state = lock.lock().unwrap();
+                    while *state == Some(false) {
+                        state = condvar.wait(state).unwrap();
+                    }
+                    if state.is_none() {
+                        return;
+                    }
+                    drop(state);
+
+                    thread::sleep(frame_rate);
+                    if *lock.lock().unwrap() == Some(true) && tx.send(Event::Frame).is_err() {
+                        return;
+                    }
                 }
             })
         };
         Events {
             rx,
-            ignore_exit_key,
             _input_handle: input_handle,
             _tick_handle: tick_handle,
+            send_frame,
+            _frame_handle: frame_handle,
         }
     }
+}
 
-    pub fn next(&self) -> Result<Event<Key>, mpsc::RecvError> {
-        self.rx.recv()
+impl EventSource for Events {
+    fn next(&mut self) -> Option<Event<Key>> {
+        self.rx.recv().ok()
     }
 
-    #[allow(dead_code)]
-    pub fn disable_exit_key(&mut self) {
-        self.ignore_exit_key.store(true, Ordering::Relaxed);
+    fn enable_frame(&mut self) {
+        self.switch_frame(Some(true));
     }
 
-    #[allow(dead_code)]
-    pub fn enable_exit_key(&mut self) {
-        self.ignore_exit_key.store(false, Ordering::Relaxed);
+    fn disable_frame(&mut self) {
+        self.switch_frame(Some(false));
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        // the frame thread ends even while frames are disabled
+        self.switch_frame(None);
     }
 }
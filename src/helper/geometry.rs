@@ -0,0 +1,140 @@
+use rand::Rng;
+use std::ops::Range;
+
+/// Most rows of a ladder, rows are indexed by `u8` in paths
+pub const MAX_ROWS: u16 = u8::MAX as u16;
+
+/// Shape of the ladder
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geometry {
+    /// rows where bridges can be, from the top
+    pub rows: u16,
+    pub min_bridges: u8,
+    /// inclusive
+    pub max_bridges: u8,
+    /// percentage of the rows of all gaps which have a bridge, instead of a
+    /// random number of bridges in every gap
+    pub density: Option<u8>,
+    /// bridges of neighbouring gaps can be in the same row, a player on the
+    /// left goes across the whole line then
+    pub share_rows: bool,
+    /// bridges can be in the very top and bottom rows
+    pub edge_rows: bool,
+}
+
+impl Default for Geometry {
+    fn default() -> Geometry {
+        Geometry {
+            rows: 10,
+            min_bridges: 2,
+            max_bridges: 5,
+            density: None,
+            share_rows: false,
+            edge_rows: true,
+        }
+    }
+}
+
+impl Geometry {
+    /// Rows which can have a bridge
+    pub fn bridge_rows(&self) -> Range<u16> {
+        if self.edge_rows {
+            0..self.rows
+        } else {
+            1..self.rows.saturating_sub(1).max(1)
+        }
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        let number_of_rows = self.bridge_rows().len();
+
+        if self.rows == 0 || self.rows > MAX_ROWS {
+            Err(format!(
+                "rows should be between 1 and {}, got: {}",
+                MAX_ROWS, self.rows
+            ))
+        } else if self.min_bridges > self.max_bridges {
+            Err(format!(
+                "min bridges {} is larger than max bridges {}",
+                self.min_bridges, self.max_bridges
+            ))
+        } else if self.max_bridges as usize > number_of_rows {
+            Err(format!(
+                "max bridges {} is larger than the rows which can have a bridge, {}",
+                self.max_bridges, number_of_rows
+            ))
+        } else if !self.share_rows
+            && self.min_bridges as usize + self.max_bridges as usize > number_of_rows
+        {
+            Err(format!(
+                "min bridges {} and max bridges {} of neighbouring gaps do not fit in the {} \
+                 rows which can have a bridge, without shared rows",
+                self.min_bridges, self.max_bridges, number_of_rows
+            ))
+        } else if self.density.is_some_and(|density| density > 100) {
+            Err(format!(
+                "density should be a percentage, got: {}",
+                self.density.unwrap_or(0)
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of bridges of each gap between `min_bridges` and `max_bridges`.
+    /// With `density`, the total is the percentage of the rows of all gaps and
+    /// is spread over random gaps. Unless `share_rows`, neighbouring gaps have
+    /// at most as many bridges as the rows together.
+    pub fn bridge_counts<R: Rng + ?Sized>(&self, number_of_gaps: u16, rng: &mut R) -> Vec<usize> {
+        let (min_bridges, max_bridges) = (self.min_bridges as usize, self.max_bridges as usize);
+        let number_of_rows = self.bridge_rows().len();
+        // most bridges of a gap next to one with `count` bridges
+        let room = |count: usize| {
+            if self.share_rows {
+                max_bridges
+            } else {
+                max_bridges.min(number_of_rows.saturating_sub(count))
+            }
+        };
+
+        let density = match self.density {
+            Some(density) => density as usize,
+            None => {
+                let mut counts: Vec<usize> = Vec::new();
+                for _ in 0..number_of_gaps {
+                    let max_bridges = room(counts.last().copied().unwrap_or(0)).max(min_bridges);
+                    counts.push(rng.gen_range(min_bridges, max_bridges + 1));
+                }
+
+                return counts;
+            }
+        };
+
+        let number_of_slots = number_of_gaps as usize * number_of_rows;
+        let total = ((number_of_slots * density + 50) / 100).clamp(
+            min_bridges * number_of_gaps as usize,
+            max_bridges * number_of_gaps as usize,
+        );
+
+        // the total is capped once neighbouring gaps have no room left
+        let mut counts = vec![min_bridges; number_of_gaps as usize];
+        for _ in counts.iter().sum::<usize>()..total {
+            let gaps: Vec<usize> = (0..counts.len())
+                .filter(|gap| {
+                    [gap.checked_sub(1), Some(gap + 1)]
+                        .iter()
+                        .flatten()
+                        .filter_map(|neighbour| counts.get(*neighbour))
+                        .fold(max_bridges, |most, count| most.min(room(*count)))
+                        > counts[*gap]
+                })
+                .collect();
+            if gaps.is_empty() {
+                break;
+            }
+            counts[gaps[rng.gen_range(0, gaps.len())]] += 1;
+        }
+
+        counts
+    }
+}
@@ -0,0 +1,123 @@
+use crate::helper::{
+    calc_draft_rounds, calc_pairings, calc_path_hashmap, calc_result_lines, calc_teams,
+    format_bracket, format_bracket_json, format_draft, format_draft_json, format_teams, json_array,
+    json_string, write_history, write_report, Point, Round, SadariEnvironment,
+};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// `{"pairings":[{"name":..,"result":..}]}`, with `"teams":[{"name":..,"members":[..]}]`
+/// in the team mode
+pub fn format_json(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    result_vec: &[String],
+    with_teams: bool,
+) -> String {
+    let pairings = calc_pairings(path_hashmap)
+        .into_iter()
+        .map(|(name_index, result_index)| {
+            format!(
+                "{{\"name\":{},\"result\":{}}}",
+                json_string(&name_vec[name_index as usize]),
+                json_string(&result_vec[result_index as usize])
+            )
+        })
+        .collect();
+    let mut fields = vec![format!("\"pairings\":{}", json_array(pairings))];
+
+    if with_teams {
+        let teams = calc_teams(path_hashmap, name_vec, result_vec)
+            .into_iter()
+            .map(|(team, members)| {
+                format!(
+                    "{{\"name\":{},\"members\":{}}}",
+                    json_string(&team),
+                    json_array(members.iter().map(|name| json_string(name)).collect())
+                )
+            })
+            .collect();
+        fields.push(format!("\"teams\":{}", json_array(teams)));
+    }
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Prints the result without the terminal screen, e.g. for scripts. Notes go
+/// to `err`, so that `out` is kept for the result, e.g. valid JSON.
+pub fn run_headless<W, E>(mut out: W, mut err: E, sadari_env: SadariEnvironment) -> io::Result<()>
+where
+    W: Write,
+    E: Write,
+{
+    if sadari_env.name_vec.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "names and results should be given by file in the headless mode",
+        ));
+    }
+
+    if let Some(note) = &sadari_env.avoidance_note {
+        writeln!(err, "note: {}", note)?;
+    }
+
+    let mut sadari_env = sadari_env;
+    let mut rng = sadari_env.rng();
+
+    loop {
+        let bridge_hashmap = sadari_env
+            .bridge_hashmap(&mut rng)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let path_hashmap = calc_path_hashmap(
+            sadari_env.number_of_blocks,
+            &bridge_hashmap,
+            sadari_env.geometry.rows,
+        );
+
+        // every round is played until the winner remains
+        if sadari_env.tournament {
+            let round = Round::new(
+                sadari_env.round,
+                &path_hashmap,
+                &sadari_env.name_vec,
+                &sadari_env.result_vec,
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            if !round.is_final() {
+                sadari_env = sadari_env.next_round(round);
+                continue;
+            }
+            sadari_env.rounds.push(round);
+        }
+
+        let (name_vec, result_vec) = (&sadari_env.name_vec, &sadari_env.result_vec);
+        let draft = calc_draft_rounds(&path_hashmap, name_vec, sadari_env.snake);
+        let text = if sadari_env.json && sadari_env.tournament {
+            format_bracket_json(&sadari_env.rounds)
+        } else if sadari_env.json && sadari_env.draft {
+            format_draft_json(&draft)
+        } else if sadari_env.json {
+            format_json(
+                &path_hashmap,
+                name_vec,
+                result_vec,
+                sadari_env.teams.is_some(),
+            )
+        } else if sadari_env.tournament {
+            format_bracket(&sadari_env.rounds).join("\n")
+        } else if sadari_env.draft {
+            format_draft(&draft).join("\n")
+        } else if sadari_env.teams.is_some() {
+            format_teams(&calc_teams(&path_hashmap, name_vec, result_vec))
+        } else {
+            calc_result_lines(&path_hashmap, name_vec, result_vec).join("\n")
+        };
+        writeln!(out, "{}", text)?;
+        out.flush()?;
+
+        write_report(&sadari_env, &bridge_hashmap, &path_hashmap)?;
+        return write_history(&sadari_env, &path_hashmap);
+    }
+}
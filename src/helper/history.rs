@@ -0,0 +1,147 @@
+use crate::helper::{calc_pairings, Point, SadariEnvironment};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+/// History file used by `--avoid-recent` without `--history`
+pub const DEFAULT_HISTORY_FILE: &str = "sadari-history.txt";
+
+/// Past draws, the oldest first. Each line of the file is a draw like
+/// `Alice = Night, Bob = Day`, where `\`, `,`, `=` and `#` in names and
+/// results are escaped by `\`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    pub draws: Vec<Vec<(String, String)>>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History, String> {
+        let mut draws = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let draw = split_escaped(line, ',')
+                .into_iter()
+                .map(|pair| match split_escaped(pair, '=').as_slice() {
+                    [name, result] => Ok((unescape_field(name), unescape_field(result))),
+                    _ => Err(format!("line {}: expected `name = result, ...`", i + 1)),
+                })
+                .collect::<Result<Vec<(String, String)>, String>>()?;
+            draws.push(draw);
+        }
+
+        Ok(History { draws })
+    }
+
+    /// Whether `name` got `result` in one of the last `number_of_draws` draws
+    pub fn is_recent(&self, number_of_draws: usize, name: &str, result: &str) -> bool {
+        self.draws
+            .iter()
+            .rev()
+            .take(number_of_draws)
+            .any(|draw| draw.iter().any(|(a, b)| a == name && b == result))
+    }
+}
+
+/// Parts of `text` between `separator`s which are not escaped by `\`
+fn split_escaped(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut is_escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if is_escaped {
+            is_escaped = false;
+        } else if c == '\\' {
+            is_escaped = true;
+        } else if c == separator {
+            parts.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+
+    parts
+}
+
+fn escape_field(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        if let '\\' | ',' | '=' | '#' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn unescape_field(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/// History in `filename`, empty if there is no file yet
+pub fn read_history_file(filename: &str) -> History {
+    let text = match fs::read_to_string(filename) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return History::default(),
+        Err(err) => panic!("\n\tread history file error : {}", err),
+    };
+
+    History::parse(&text).unwrap_or_else(|err| panic!("\n\thistory file {}, {}", filename, err))
+}
+
+/// A line of the history file
+pub fn format_draw(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    result_vec: &[String],
+) -> String {
+    calc_pairings(path_hashmap)
+        .into_iter()
+        .map(|(name_index, result_index)| {
+            format!(
+                "{} = {}",
+                escape_field(&name_vec[name_index as usize]),
+                escape_field(&result_vec[result_index as usize])
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Appends the final draw to the history file if it is used
+pub fn write_history(
+    sadari_env: &SadariEnvironment,
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+) -> io::Result<()> {
+    let filename = match &sadari_env.history_file {
+        Some(filename) => filename,
+        None => return Ok(()),
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+    writeln!(
+        file,
+        "{}",
+        format_draw(path_hashmap, &sadari_env.name_vec, &sadari_env.result_vec)
+    )
+}
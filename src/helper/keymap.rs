@@ -0,0 +1,345 @@
+use crate::helper::Key;
+use std::{fs, io};
+
+/// Actions of the sadari screen and the result page which can be bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    ToggleResult,
+    Prev,
+    Next,
+    Start,
+    FastForward,
+    Pause,
+    Step,
+    SpeedUp,
+    SpeedDown,
+    EditName,
+    EditResult,
+    NewLadder,
+    /// next round of the tournament
+    NextRound,
+    /// search names in the result page
+    Search,
+    /// next order of the pairings in the result page
+    Order,
+    /// groups of identical results in the result page
+    Group,
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    /// first page of the pairings
+    Top,
+    /// last page of the pairings
+    Bottom,
+    /// copy the mapping to the clipboard as plain text
+    Copy,
+    /// copy the mapping to the clipboard as a Markdown table
+    CopyMarkdown,
+}
+
+const ACTIONS: [(Action, &str); 25] = [
+    (Action::Quit, "quit"),
+    (Action::ToggleResult, "toggle_result"),
+    (Action::Prev, "prev"),
+    (Action::Next, "next"),
+    (Action::Start, "start"),
+    (Action::FastForward, "fast_forward"),
+    (Action::Pause, "pause"),
+    (Action::Step, "step"),
+    (Action::SpeedUp, "speed_up"),
+    (Action::SpeedDown, "speed_down"),
+    (Action::EditName, "edit_name"),
+    (Action::EditResult, "edit_result"),
+    (Action::NewLadder, "new_ladder"),
+    (Action::NextRound, "next_round"),
+    (Action::Search, "search"),
+    (Action::Order, "order"),
+    (Action::Group, "group"),
+    (Action::ScrollUp, "scroll_up"),
+    (Action::ScrollDown, "scroll_down"),
+    (Action::PageUp, "page_up"),
+    (Action::PageDown, "page_down"),
+    (Action::Top, "top"),
+    (Action::Bottom, "bottom"),
+    (Action::Copy, "copy"),
+    (Action::CopyMarkdown, "copy_markdown"),
+];
+
+/// Entries of the guide text, actions sharing one description
+const GUIDE: [(&[Action], &str); 10] = [
+    (&[Action::Prev, Action::Next], "Left, Right"),
+    (&[Action::Start], "Start"),
+    (&[Action::Pause], "Pause, Resume"),
+    (&[Action::FastForward], "Fast-forward"),
+    (&[Action::Step], "Step one segment"),
+    (&[Action::SpeedUp, Action::SpeedDown], "Speed up, down"),
+    (&[Action::EditName, Action::EditResult], "Edit name, result"),
+    (&[Action::NewLadder], "New ladder"),
+    (&[Action::ToggleResult], "Go to result"),
+    (&[Action::Quit], "Quit"),
+];
+
+const NAMED_KEYS: [(Key, &str); 17] = [
+    (Key::Char('\n'), "enter"),
+    (Key::Char('\t'), "tab"),
+    (Key::Char(' '), "space"),
+    (Key::Char(','), "comma"),
+    (Key::Backspace, "backspace"),
+    (Key::Left, "left"),
+    (Key::Right, "right"),
+    (Key::Up, "up"),
+    (Key::Down, "down"),
+    (Key::Home, "home"),
+    (Key::End, "end"),
+    (Key::PageUp, "pageup"),
+    (Key::PageDown, "pagedown"),
+    (Key::BackTab, "backtab"),
+    (Key::Delete, "delete"),
+    (Key::Insert, "insert"),
+    (Key::Esc, "esc"),
+];
+
+/// Keys bound to each action. A key belongs to one action at most.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap {
+            bindings: vec![
+                (Action::Quit, vec![Key::Char('q'), Key::Ctrl('c')]),
+                (Action::ToggleResult, vec![Key::Char('r')]),
+                (Action::Prev, vec![Key::Left, Key::Char('h')]),
+                (Action::Next, vec![Key::Right, Key::Char('l')]),
+                (Action::Start, vec![Key::Char('s'), Key::Char('\n')]),
+                (Action::FastForward, vec![Key::Char('f')]),
+                (Action::Pause, vec![Key::Char(' ')]),
+                (Action::Step, vec![Key::Char('.')]),
+                (Action::SpeedUp, vec![Key::Char('+'), Key::Char('=')]),
+                (Action::SpeedDown, vec![Key::Char('-')]),
+                (Action::EditName, vec![Key::Char('e')]),
+                (Action::EditResult, vec![Key::Char('E')]),
+                (Action::NewLadder, vec![Key::Char('n')]),
+                (Action::NextRound, vec![Key::Char('N')]),
+                (Action::Search, vec![Key::Char('/')]),
+                (Action::Order, vec![Key::Char('o')]),
+                (Action::Group, vec![Key::Char('g')]),
+                (Action::ScrollUp, vec![Key::Up, Key::Char('k')]),
+                (Action::ScrollDown, vec![Key::Down, Key::Char('j')]),
+                (Action::PageUp, vec![Key::PageUp]),
+                (Action::PageDown, vec![Key::PageDown]),
+                (Action::Top, vec![Key::Home]),
+                (Action::Bottom, vec![Key::End]),
+                (Action::Copy, vec![Key::Char('c')]),
+                (Action::CopyMarkdown, vec![Key::Char('m')]),
+            ],
+        }
+    }
+}
+
+impl Keymap {
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Names of the keys of `action` joined by `/`, e.g. `q/ctrl+c`
+    pub fn key_names(&self, action: Action) -> String {
+        let names: Vec<String> = self.keys(action).iter().map(|key| key_name(*key)).collect();
+
+        names.join("/")
+    }
+
+    /// Binds `keys` to `action`, the keys are taken away from other actions
+    pub fn bind(&mut self, action: Action, keys: Vec<Key>) {
+        for (a, bound_keys) in self.bindings.iter_mut() {
+            if *a == action {
+                *bound_keys = keys.clone();
+            } else {
+                bound_keys.retain(|key| !keys.contains(key));
+            }
+        }
+    }
+
+    /// Keys which stop a replay, see `read_exit_key`, every key of quit
+    pub fn exit_keys(&self) -> Vec<Key> {
+        match self.keys(Action::Quit) {
+            [] => vec![Key::Ctrl('c')],
+            keys => keys.to_vec(),
+        }
+    }
+
+    /// Reads bindings over the default ones. Each line is `action = key, key`,
+    /// and lines starting with `#` are comments.
+    pub fn parse(text: &str) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.splitn(2, '=');
+            let (name, keys) = match (split.next(), split.next()) {
+                (Some(name), Some(keys)) => (name.trim(), keys),
+                _ => return Err(format!("line {}: expected `action = key, key`", i + 1)),
+            };
+
+            let action = ACTIONS
+                .iter()
+                .find(|(_, action_name)| *action_name == name)
+                .map(|(action, _)| *action)
+                .ok_or_else(|| format!("line {}: unknown action {}", i + 1, name))?;
+
+            let keys = keys
+                .split(',')
+                .map(|key| parse_key(key.trim()))
+                .collect::<Result<Vec<Key>, String>>()
+                .map_err(|err| format!("line {}: {}", i + 1, err))?;
+
+            keymap.bind(action, keys);
+        }
+
+        if keymap.keys(Action::Quit).is_empty() {
+            return Err(String::from("quit should have a key at least"));
+        }
+
+        Ok(keymap)
+    }
+
+    /// Guide text of the sadari screen, two entries in a line
+    pub fn guide_text(&self) -> String {
+        let entries: Vec<(String, &str)> = GUIDE
+            .iter()
+            .map(|(actions, description)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .map(|action| self.key_names(*action))
+                    .collect();
+
+                (keys.join(", "), *description)
+            })
+            .collect();
+
+        // widths of keys and descriptions in each column
+        let width = |column: usize, of_keys: bool| -> usize {
+            entries
+                .iter()
+                .skip(column)
+                .step_by(2)
+                .map(|(keys, description)| match of_keys {
+                    true => keys.chars().count(),
+                    false => description.chars().count(),
+                })
+                .max()
+                .unwrap_or(0)
+        };
+        let key_widths = [width(0, true), width(1, true)];
+        let description_widths = [width(0, false), width(1, false)];
+
+        let lines: Vec<String> = entries
+            .chunks(2)
+            .map(|chunk| {
+                let columns: Vec<String> = chunk
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (keys, description))| {
+                        format!(
+                            "{:key_width$} : {:description_width$}",
+                            keys,
+                            description,
+                            key_width = key_widths[i],
+                            description_width = description_widths[i]
+                        )
+                    })
+                    .collect();
+
+                // keep lines in the same width, so that they are aligned when centered
+                format!(
+                    "{:width$}",
+                    columns.join("   "),
+                    width = key_widths.iter().sum::<usize>()
+                        + description_widths.iter().sum::<usize>()
+                        + 9
+                )
+            })
+            .collect();
+
+        lines.join("\n")
+    }
+}
+
+pub fn parse_key(text: &str) -> Result<Key, String> {
+    let lower = text.to_lowercase();
+
+    if let Some((key, _)) = NAMED_KEYS.iter().find(|(_, name)| *name == lower) {
+        return Ok(*key);
+    }
+
+    let single_char = |text: &str| -> Option<char> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    };
+
+    let key = if let Some(c) = single_char(text) {
+        Some(Key::Char(c))
+    } else if let Some(c) = lower.strip_prefix("ctrl+").and_then(single_char) {
+        Some(Key::Ctrl(c))
+    } else if let Some(c) = text.get(4..).filter(|_| lower.starts_with("alt+")) {
+        single_char(c).map(Key::Alt)
+    } else {
+        lower
+            .strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|n| (1..=12).contains(n))
+            .map(Key::F)
+    };
+
+    key.ok_or_else(|| format!("unknown key {}", text))
+}
+
+pub fn key_name(key: Key) -> String {
+    match key {
+        Key::Left => String::from("←"),
+        Key::Right => String::from("→"),
+        Key::Up => String::from("↑"),
+        Key::Down => String::from("↓"),
+        Key::Char(c) if !NAMED_KEYS.iter().any(|(key, _)| *key == Key::Char(c)) => c.to_string(),
+        Key::Ctrl(c) => format!("ctrl+{}", c),
+        Key::Alt(c) => format!("alt+{}", c),
+        Key::F(n) => format!("f{}", n),
+        _ => NAMED_KEYS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, name)| String::from(*name))
+            .unwrap_or_default(),
+    }
+}
+
+pub fn read_keymap_file(filename: &str) -> Keymap {
+    let text = fs::read_to_string(filename).unwrap_or_else(|err: io::Error| {
+        panic!("\n\tkeymap file error : {}", err);
+    });
+
+    Keymap::parse(&text).unwrap_or_else(|err| {
+        panic!("\n\tkeymap file error, {}", err);
+    })
+}
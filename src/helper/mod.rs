@@ -1,11 +1,47 @@
+mod accessible;
+mod animation;
+mod app;
+mod backend;
 mod calc;
+mod clipboard;
+mod constraint;
+mod draft;
 mod event;
+mod geometry;
+mod history;
+mod keymap;
 #[allow(dead_code)]
 mod tools;
 
 mod draw;
+mod headless;
+mod report;
+mod result_page;
+mod script;
+mod setup;
+mod teams;
+mod theme;
+mod tournament;
 
+pub use accessible::*;
+pub use animation::*;
+pub use app::*;
+pub use backend::*;
 pub use calc::*;
+pub use clipboard::*;
+pub use constraint::*;
+pub use draft::*;
 pub use draw::*;
 pub use event::*;
+pub use geometry::*;
+pub use headless::*;
+pub use history::*;
+pub use keymap::*;
+pub use report::*;
+pub use result_page::*;
+pub use script::*;
+pub use setup::*;
+pub use teams::*;
+pub use theme::*;
 pub use tools::*;
+pub use tournament::*;
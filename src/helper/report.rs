@@ -0,0 +1,351 @@
+use crate::helper::{
+    calc_draft_rounds, calc_pairings, calc_teams, format_bracket, format_draft, Point, Round,
+    SadariEnvironment,
+};
+use std::{
+    collections::HashMap,
+    fs, io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const REPORT_TITLE: &str = "Rust-Sadari-Cli result";
+
+/// Format of the report written after the draw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Plain,
+    Csv,
+}
+
+const REPORT_FORMATS: [(ReportFormat, &str); 3] = [
+    (ReportFormat::Markdown, "markdown"),
+    (ReportFormat::Plain, "plain"),
+    (ReportFormat::Csv, "csv"),
+];
+
+impl ReportFormat {
+    pub fn by_name(name: &str) -> Option<ReportFormat> {
+        REPORT_FORMATS
+            .iter()
+            .find(|(_, format_name)| *format_name == name)
+            .map(|(format, _)| *format)
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        REPORT_FORMATS.iter().map(|(_, name)| *name).collect()
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Plain => "txt",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Everything written in a report
+pub struct Report<'a> {
+    pub date: String,
+    pub seed: Option<u64>,
+    pub bridge_hashmap: &'a HashMap<u16, Vec<u16>>,
+    pub name_vec: &'a [String],
+    pub result_vec: &'a [String],
+    /// (name index, result index), see `calc_pairings`
+    pub pairings: Vec<(u8, u8)>,
+    /// members of every team in the team mode
+    pub teams: Option<Vec<(String, Vec<String>)>>,
+    /// finished rounds of the tournament
+    pub rounds: &'a [Round],
+    /// names in the order of each round in the draft mode
+    pub draft: Option<Vec<Vec<String>>>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(
+        sadari_env: &'a SadariEnvironment,
+        bridge_hashmap: &'a HashMap<u16, Vec<u16>>,
+        path_hashmap: &HashMap<u8, Vec<Point>>,
+    ) -> Report<'a> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        Report {
+            date: format_utc_date(now),
+            seed: sadari_env.seed,
+            bridge_hashmap,
+            name_vec: &sadari_env.name_vec,
+            result_vec: &sadari_env.result_vec,
+            pairings: calc_pairings(path_hashmap),
+            teams: sadari_env
+                .teams
+                .map(|_| calc_teams(path_hashmap, &sadari_env.name_vec, &sadari_env.result_vec)),
+            rounds: &sadari_env.rounds,
+            draft: if sadari_env.draft {
+                Some(calc_draft_rounds(
+                    path_hashmap,
+                    &sadari_env.name_vec,
+                    sadari_env.snake,
+                ))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Rows of bridges in each gap, e.g. `1-2: 3, 5, 9`, rows start from 1
+    fn bridge_lines(&self) -> Vec<String> {
+        let mut gaps: Vec<(&u16, &Vec<u16>)> = self.bridge_hashmap.iter().collect();
+        gaps.sort();
+
+        gaps.into_iter()
+            .map(|(gap, rows)| {
+                let rows: Vec<String> = rows.iter().map(|row| (row + 1).to_string()).collect();
+                format!("{}-{}: {}", gap + 1, gap + 2, rows.join(", "))
+            })
+            .collect()
+    }
+
+    fn mapping(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.pairings.iter().map(move |(name_index, result_index)| {
+            (
+                &self.name_vec[*name_index as usize],
+                &self.result_vec[*result_index as usize],
+            )
+        })
+    }
+
+    fn seed_text(&self) -> String {
+        self.seed
+            .map(|seed| seed.to_string())
+            .unwrap_or_else(|| String::from("random"))
+    }
+
+    pub fn format(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.markdown(),
+            ReportFormat::Plain => self.plain(),
+            ReportFormat::Csv => self.csv(),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let mut lines = vec![
+            format!("# {}", REPORT_TITLE),
+            String::new(),
+            format!("- Date: {}", self.date),
+            format!("- Seed: {}", self.seed_text()),
+            format!("- Participants: {}", self.name_vec.join(", ")),
+            String::new(),
+            String::from("## Bridges"),
+            String::new(),
+        ];
+        lines.extend(self.bridge_lines().iter().map(|line| format!("- {}", line)));
+        lines.extend(vec![
+            String::new(),
+            String::from("## Mapping"),
+            String::new(),
+            String::from("| Name | Result |"),
+            String::from("| --- | --- |"),
+        ]);
+        lines.extend(self.mapping().map(|(name, result)| {
+            format!("| {} | {} |", markdown_cell(name), markdown_cell(result))
+        }));
+        if let Some(teams) = &self.teams {
+            lines.extend(vec![String::new(), String::from("## Teams"), String::new()]);
+            lines.extend(
+                teams
+                    .iter()
+                    .map(|(team, members)| format!("- {}: {}", team, members.join(", "))),
+            );
+        }
+        if !self.rounds.is_empty() {
+            lines.extend(vec![
+                String::new(),
+                String::from("## Tournament"),
+                String::new(),
+            ]);
+            lines.extend(
+                format_bracket(self.rounds)
+                    .iter()
+                    .map(|line| format!("- {}", line)),
+            );
+        }
+        if let Some(draft) = &self.draft {
+            lines.extend(vec![
+                String::new(),
+                String::from("## Draft order"),
+                String::new(),
+            ]);
+            lines.extend(format_draft(draft).iter().map(|line| format!("- {}", line)));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    fn plain(&self) -> String {
+        let mut lines = vec![
+            String::from(REPORT_TITLE),
+            String::new(),
+            format!("Date: {}", self.date),
+            format!("Seed: {}", self.seed_text()),
+            format!("Participants: {}", self.name_vec.join(", ")),
+            String::new(),
+            String::from("Bridges"),
+        ];
+        lines.extend(self.bridge_lines().iter().map(|line| format!("  {}", line)));
+        lines.extend(vec![String::new(), String::from("Mapping")]);
+        lines.extend(
+            self.mapping()
+                .map(|(name, result)| format!("  {} -> {}", name, result)),
+        );
+        if let Some(teams) = &self.teams {
+            lines.extend(vec![String::new(), String::from("Teams")]);
+            lines.extend(
+                teams
+                    .iter()
+                    .map(|(team, members)| format!("  {}: {}", team, members.join(", "))),
+            );
+        }
+        if !self.rounds.is_empty() {
+            lines.extend(vec![String::new(), String::from("Tournament")]);
+            lines.extend(
+                format_bracket(self.rounds)
+                    .iter()
+                    .map(|line| format!("  {}", line)),
+            );
+        }
+        if let Some(draft) = &self.draft {
+            lines.extend(vec![String::new(), String::from("Draft order")]);
+            lines.extend(format_draft(draft).iter().map(|line| format!("  {}", line)));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Fields first as `key,value` rows, then the mapping as a `name,result` table
+    fn csv(&self) -> String {
+        let mut rows = vec![
+            vec![String::from("title"), String::from(REPORT_TITLE)],
+            vec![String::from("date"), self.date.clone()],
+            vec![String::from("seed"), self.seed_text()],
+            vec![String::from("participants"), self.name_vec.join("; ")],
+            vec![String::from("bridges"), self.bridge_lines().join("; ")],
+        ];
+        if !self.rounds.is_empty() {
+            rows.push(vec![
+                String::from("tournament"),
+                format_bracket(self.rounds).join("; "),
+            ]);
+        }
+        if let Some(draft) = &self.draft {
+            rows.push(vec![String::from("draft"), format_draft(draft).join("; ")]);
+        }
+        rows.extend(vec![
+            vec![],
+            vec![String::from("name"), String::from("result")],
+        ]);
+        rows.extend(
+            self.mapping()
+                .map(|(name, result)| vec![name.clone(), result.clone()]),
+        );
+
+        rows.iter()
+            .map(|row| {
+                let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+                fields.join(",")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+            + "\n"
+    }
+}
+
+/// `text` as a JSON string with quotes
+pub fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+pub fn json_array(items: Vec<String>) -> String {
+    format!("[{}]", items.join(","))
+}
+
+/// Escapes `text` for a cell of a Markdown table. `|` would end the cell, and
+/// `\` is escaped first, or `\|` in the text would end it all the same.
+pub fn markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+/// Quotes `field` if it has a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// `YYYY-MM-DD hh:mm:ss UTC` of the unix time `secs`
+pub fn format_utc_date(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let secs_of_day = secs % 86400;
+
+    // days to the civil date, from Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Writes the report of the final ladder if `--report` is given
+pub fn write_report(
+    sadari_env: &SadariEnvironment,
+    bridge_hashmap: &HashMap<u16, Vec<u16>>,
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+) -> io::Result<()> {
+    let format = match sadari_env.report {
+        Some(format) => format,
+        None => return Ok(()),
+    };
+    let filename = sadari_env
+        .report_file
+        .clone()
+        .unwrap_or_else(|| format!("sadari-report.{}", format.extension()));
+
+    let report = Report::new(sadari_env, bridge_hashmap, path_hashmap);
+    fs::write(filename, report.format(format))
+}
@@ -0,0 +1,206 @@
+use crate::helper::{
+    calc_pairings, calc_result_row_index, Action, CopyFormat, Key, Keymap, Point, ResultLayout,
+    SadariEnvironment,
+};
+use std::collections::HashMap;
+
+/// Order of the pairings in the result page
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResultOrder {
+    #[default]
+    Input,
+    Name,
+    Result,
+}
+
+impl ResultOrder {
+    fn next(self) -> ResultOrder {
+        match self {
+            ResultOrder::Input => ResultOrder::Name,
+            ResultOrder::Name => ResultOrder::Result,
+            ResultOrder::Result => ResultOrder::Input,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ResultOrder::Input => "input",
+            ResultOrder::Name => "name",
+            ResultOrder::Result => "result",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResultAction {
+    Stay,
+    /// mapping should be copied to the clipboard
+    Copy(CopyFormat),
+    /// key is not used by the result page
+    Ignored,
+}
+
+/// Numbers first in the order of values, e.g. positions of the draft mode,
+/// then texts ignoring case
+fn sort_key(text: &str) -> (bool, u64, String) {
+    match text.parse::<u64>() {
+        Ok(number) => (false, number, String::new()),
+        Err(_) => (true, 0, text.to_lowercase()),
+    }
+}
+
+/// One pairing shown in the result page
+#[derive(Debug, PartialEq)]
+pub struct ResultRow {
+    pub name_index: u8,
+    pub result_index: u8,
+    /// number of players getting the same result, only on the first row of
+    /// each group
+    pub group_size: Option<usize>,
+}
+
+/// State of the result page, the pairings are sorted, grouped by identical
+/// results and filtered by the search query
+#[derive(Debug, Default)]
+pub struct ResultState {
+    pub order: ResultOrder,
+    pub grouped: bool,
+    pub query: String,
+    /// search box is taking the keys
+    pub searching: bool,
+    /// index of the first visible row
+    pub scroll: usize,
+    /// name index of the pairing highlighted by a click
+    pub highlighted: Option<u8>,
+    /// message shown in the status line until the next key
+    pub notice: Option<String>,
+    /// number of rows fitting in the page, updated when it is drawn
+    page_rows: usize,
+}
+
+impl ResultState {
+    pub fn new() -> ResultState {
+        ResultState::default()
+    }
+
+    /// Pairings in the order to be shown
+    pub fn rows(
+        &self,
+        sadari_env: &SadariEnvironment,
+        path_hashmap: &HashMap<u8, Vec<Point>>,
+    ) -> Vec<ResultRow> {
+        let name = |row: &ResultRow| &sadari_env.name_vec[row.name_index as usize];
+        let result = |row: &ResultRow| &sadari_env.result_vec[row.result_index as usize];
+        let query = self.query.to_lowercase();
+
+        let mut rows: Vec<ResultRow> = calc_pairings(path_hashmap)
+            .into_iter()
+            .map(|(name_index, result_index)| ResultRow {
+                name_index,
+                result_index,
+                group_size: None,
+            })
+            .filter(|row| name(row).to_lowercase().contains(&query))
+            .collect();
+
+        match self.order {
+            ResultOrder::Input => {}
+            ResultOrder::Name => rows.sort_by_key(|row| sort_key(name(row))),
+            ResultOrder::Result => rows.sort_by_key(|row| sort_key(result(row))),
+        };
+
+        if self.grouped {
+            // sorting is stable, so the order is kept in each group
+            rows.sort_by_key(|row| sort_key(result(row)));
+
+            let mut start = 0;
+            while start < rows.len() {
+                let size = rows[start..]
+                    .iter()
+                    .take_while(|row| sort_key(result(row)) == sort_key(result(&rows[start])))
+                    .count();
+                rows[start].group_size = Some(size);
+                start += size;
+            }
+        }
+
+        rows
+    }
+
+    /// Keys are typed into the search box while searching, otherwise the
+    /// actions of `keymap` are taken. Other keys are left to the app, so a
+    /// key bound to e.g. quit is never swallowed.
+    pub fn handle_key(&mut self, key: Key, keymap: &Keymap) -> ResultAction {
+        self.notice = None;
+
+        if self.searching {
+            match key {
+                Key::Esc | Key::Ctrl('c') => {
+                    self.query.clear();
+                    self.searching = false;
+                }
+                Key::Char('\n') => self.searching = false,
+                Key::Backspace => {
+                    self.query.pop();
+                }
+                Key::Char(c) if !c.is_control() => self.query.push(c),
+                _ => {}
+            };
+            self.scroll = 0;
+
+            return ResultAction::Stay;
+        }
+
+        match keymap.action(key) {
+            Some(Action::Search) => self.searching = true,
+            Some(Action::Order) => {
+                self.order = self.order.next();
+                self.scroll = 0;
+            }
+            Some(Action::Group) => {
+                self.grouped = !self.grouped;
+                self.scroll = 0;
+            }
+            Some(Action::ScrollUp) => self.scroll_up(1),
+            Some(Action::ScrollDown) => self.scroll_down(1),
+            Some(Action::PageUp) => self.scroll_up(self.page_rows.max(1)),
+            Some(Action::PageDown) => self.scroll_down(self.page_rows.max(1)),
+            Some(Action::Top) => self.scroll = 0,
+            Some(Action::Bottom) => self.scroll = usize::MAX,
+            Some(Action::Copy) => return ResultAction::Copy(CopyFormat::Plain),
+            Some(Action::CopyMarkdown) => return ResultAction::Copy(CopyFormat::Markdown),
+            Some(_) => return ResultAction::Ignored,
+            None if key == Key::Esc && !self.query.is_empty() => {
+                self.query.clear();
+                self.scroll = 0;
+            }
+            None => return ResultAction::Ignored,
+        };
+
+        ResultAction::Stay
+    }
+
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Scroll is limited when the page is drawn, see `clamp_scroll`
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_add(rows);
+    }
+
+    /// Keeps the last page filled with rows
+    pub fn clamp_scroll(&mut self, number_of_rows: usize, page_rows: usize) {
+        self.page_rows = page_rows;
+        self.scroll = self.scroll.min(number_of_rows.saturating_sub(page_rows));
+    }
+
+    /// Name index of the pairing at terminal coordinate (`x`, `y`)
+    pub fn row_at(&self, layout: &ResultLayout, rows: &[ResultRow], x: u16, y: u16) -> Option<u8> {
+        let number_of_rows = rows.len().saturating_sub(self.scroll).min(u8::MAX as usize);
+
+        calc_result_row_index(layout, number_of_rows as u8, x, y)
+            .and_then(|row| rows.get(self.scroll + row as usize))
+            .map(|row| row.name_index)
+    }
+}
@@ -0,0 +1,196 @@
+use crate::helper::{parse_key, Event, EventSource, Key, Mouse};
+use std::{
+    collections::VecDeque,
+    fs, io,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Step of a script driving the app instead of the terminal input
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptStep {
+    Event(Event<Key>),
+    /// time passes, with frames while the path is drawn
+    Wait(Duration),
+}
+
+/// Reads a script, one step in each line:
+///
+/// - `key <key> ...`: keys named like in the keymap file, e.g. `key s enter`
+/// - `type <text>`: every character of the text as a key
+/// - `wait <ms>`: time passes, the animation goes on meanwhile
+/// - `tick`: a tick of the event loop
+/// - `click <x> <y>`, `scroll-up <x> <y>`, `scroll-down <x> <y>`: mouse
+///
+/// Empty lines and lines starting with `#` are skipped.
+pub fn parse_script(text: &str) -> Result<Vec<ScriptStep>, String> {
+    let mut steps = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut split = line.splitn(2, ' ');
+        let command = split.next().unwrap_or("");
+        let args = split.next().unwrap_or("").trim();
+        let error = |message: &str| format!("line {}: {}, got: {}", i + 1, message, line);
+        let point = || -> Result<(u16, u16), String> {
+            let numbers: Vec<u16> = args
+                .split_whitespace()
+                .map(|x| x.parse::<u16>())
+                .collect::<Result<Vec<u16>, _>>()
+                .map_err(|_| error("expected `x y`"))?;
+            match numbers.as_slice() {
+                [x, y] => Ok((*x, *y)),
+                _ => Err(error("expected `x y`")),
+            }
+        };
+
+        match command {
+            "key" => {
+                if args.is_empty() {
+                    return Err(error("expected keys"));
+                }
+                for key in args.split_whitespace() {
+                    let key = parse_key(key).map_err(|err| error(&err))?;
+                    steps.push(ScriptStep::Event(Event::Input(key)));
+                }
+            }
+            "type" => {
+                steps.extend(
+                    args.chars()
+                        .map(|c| ScriptStep::Event(Event::Input(Key::Char(c)))),
+                );
+            }
+            "wait" => {
+                let ms = args
+                    .parse::<u64>()
+                    .map_err(|_| error("expected milliseconds"))?;
+                steps.push(ScriptStep::Wait(Duration::from_millis(ms)));
+            }
+            "tick" => steps.push(ScriptStep::Event(Event::Tick)),
+            "click" => {
+                let (x, y) = point()?;
+                steps.push(ScriptStep::Event(Event::Mouse(Mouse::Press(x, y))));
+            }
+            "scroll-up" => {
+                let (x, y) = point()?;
+                steps.push(ScriptStep::Event(Event::Mouse(Mouse::ScrollUp(x, y))));
+            }
+            "scroll-down" => {
+                let (x, y) = point()?;
+                steps.push(ScriptStep::Event(Event::Mouse(Mouse::ScrollDown(x, y))));
+            }
+            _ => return Err(error("unknown step")),
+        }
+    }
+
+    Ok(steps)
+}
+
+pub fn read_script_file(filename: &str) -> Vec<ScriptStep> {
+    let text = fs::read_to_string(filename).unwrap_or_else(|err: io::Error| {
+        panic!("\n\treplay file error : {}", err);
+    });
+
+    parse_script(&text).unwrap_or_else(|err| {
+        panic!("\n\treplay file error, {}", err);
+    })
+}
+
+/// Events of a script, on a clock of their own so that the animation is the
+/// same in every run. The input is closed at the end of the script.
+pub struct ScriptedEvents {
+    steps: VecDeque<ScriptStep>,
+    frame_rate: Duration,
+    send_frame: bool,
+    clock: Instant,
+    /// waits take real time as well, e.g. for demo recordings
+    realtime: bool,
+    /// exit keys pressed while the script is played, see `read_exit_key`
+    exit_key: Option<mpsc::Receiver<Key>>,
+}
+
+impl ScriptedEvents {
+    pub fn new(steps: Vec<ScriptStep>, frame_rate: Duration) -> ScriptedEvents {
+        ScriptedEvents {
+            steps: steps.into(),
+            frame_rate,
+            send_frame: false,
+            clock: Instant::now(),
+            realtime: false,
+            exit_key: None,
+        }
+    }
+
+    pub fn realtime(mut self) -> ScriptedEvents {
+        self.realtime = true;
+
+        self
+    }
+
+    /// The script ends when a key is received from `exit_key`
+    pub fn exit_key(mut self, exit_key: mpsc::Receiver<Key>) -> ScriptedEvents {
+        self.exit_key = Some(exit_key);
+
+        self
+    }
+
+    fn pass(&mut self, duration: Duration) {
+        if self.realtime {
+            match self.exit_key.as_ref().map(|rx| rx.recv_timeout(duration)) {
+                Some(Ok(_)) => self.steps.clear(),
+                Some(Err(RecvTimeoutError::Timeout)) => {}
+                // no more exit key is sent
+                _ => thread::sleep(duration),
+            }
+        }
+        self.clock += duration;
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn next(&mut self) -> Option<Event<Key>> {
+        if let Some(Ok(_)) = self.exit_key.as_ref().map(|rx| rx.try_recv()) {
+            self.steps.clear();
+        }
+
+        loop {
+            let left = match self.steps.front_mut() {
+                Some(ScriptStep::Wait(left)) => left,
+                Some(ScriptStep::Event(_)) => match self.steps.pop_front() {
+                    Some(ScriptStep::Event(event)) => return Some(event),
+                    _ => unreachable!(),
+                },
+                None => return None,
+            };
+
+            if self.send_frame && *left >= self.frame_rate {
+                *left -= self.frame_rate;
+                let frame_rate = self.frame_rate;
+                self.pass(frame_rate);
+
+                return Some(Event::Frame);
+            }
+
+            let left = *left;
+            self.steps.pop_front();
+            self.pass(left);
+        }
+    }
+
+    fn enable_frame(&mut self) {
+        self.send_frame = true;
+    }
+
+    fn disable_frame(&mut self) {
+        self.send_frame = false;
+    }
+
+    fn now(&self) -> Instant {
+        self.clock
+    }
+}
@@ -0,0 +1,270 @@
+use crate::helper::{Key, SadariEnvironment, MAX_NUMBER_OF_BLOCKS, MIN_NUMBER_OF_BLOCKS};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetupColumn {
+    Name,
+    Result,
+}
+
+impl SetupColumn {
+    fn toggle(self) -> Self {
+        match self {
+            SetupColumn::Name => SetupColumn::Result,
+            SetupColumn::Result => SetupColumn::Name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetupFocus {
+    Field(usize, SetupColumn),
+    Start,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SetupAction {
+    Stay,
+    Start,
+    Quit,
+}
+
+/// State of the setup screen. Names and results are kept as rows of the same
+/// length, so a row can be added or removed for both columns at once.
+#[derive(Debug)]
+pub struct SetupState {
+    pub name_vec: Vec<String>,
+    pub result_vec: Vec<String>,
+    pub focus: SetupFocus,
+}
+
+impl Default for SetupState {
+    fn default() -> SetupState {
+        SetupState {
+            name_vec: vec![String::new(); MIN_NUMBER_OF_BLOCKS as usize],
+            result_vec: vec![String::new(); MIN_NUMBER_OF_BLOCKS as usize],
+            focus: SetupFocus::Field(0, SetupColumn::Name),
+        }
+    }
+}
+
+impl SetupState {
+    pub fn new() -> SetupState {
+        SetupState::default()
+    }
+
+    pub fn number_of_rows(&self) -> usize {
+        self.name_vec.len()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let len = self.number_of_rows();
+
+        if len < MIN_NUMBER_OF_BLOCKS as usize || len > MAX_NUMBER_OF_BLOCKS as usize {
+            return Err(format!(
+                "Number of rows should be {} <= rows <= {}, current: {}",
+                MIN_NUMBER_OF_BLOCKS, MAX_NUMBER_OF_BLOCKS, len
+            ));
+        }
+
+        if let Some(i) = self.name_vec.iter().position(|x| x.trim().is_empty()) {
+            return Err(format!("Name {} is empty", i + 1));
+        }
+
+        if let Some(i) = self.result_vec.iter().position(|x| x.trim().is_empty()) {
+            return Err(format!(
+                "Result {} is empty, ctrl+r fills results with numbers",
+                i + 1
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> SetupAction {
+        match key {
+            Key::Esc | Key::Ctrl('c') => return SetupAction::Quit,
+            Key::Ctrl('s') => return self.start(),
+            Key::Up => self.focus_prev(),
+            Key::Down => self.focus_next(),
+            Key::Char('\t') | Key::BackTab => {
+                if let SetupFocus::Field(row, column) = self.focus {
+                    self.focus = SetupFocus::Field(row, column.toggle());
+                }
+            }
+            Key::Char('\n') => match self.focus {
+                SetupFocus::Start => return self.start(),
+                SetupFocus::Field(row, column) => self.insert_row(row + 1, column),
+            },
+            Key::Ctrl('x') => self.remove_row(),
+            Key::Ctrl('u') => self.move_field(-1),
+            Key::Ctrl('d') => self.move_field(1),
+            Key::Ctrl('r') => {
+                self.result_vec = (0..self.number_of_rows()).map(|x| x.to_string()).collect();
+            }
+            Key::Backspace => {
+                if let Some(field) = self.focused_field_mut() {
+                    field.pop();
+                }
+            }
+            Key::Char(c) if !c.is_control() => {
+                if let Some(field) = self.focused_field_mut() {
+                    field.push(c);
+                }
+            }
+            _ => {}
+        };
+
+        SetupAction::Stay
+    }
+
+    /// Applies names and results to `sadari_env`, should be called after `validate`
+    pub fn sadari_env(&self, sadari_env: SadariEnvironment) -> SadariEnvironment {
+        let trim = |vec: &Vec<String>| -> Vec<String> {
+            vec.iter().map(|x| String::from(x.trim())).collect()
+        };
+
+        sadari_env
+            .number_of_blocks(self.number_of_rows() as u8)
+            .name_vec(trim(&self.name_vec))
+            .result_vec(trim(&self.result_vec))
+    }
+
+    fn start(&self) -> SetupAction {
+        match self.validate() {
+            Ok(()) => SetupAction::Start,
+            Err(_) => SetupAction::Stay,
+        }
+    }
+
+    fn focused_field_mut(&mut self) -> Option<&mut String> {
+        match self.focus {
+            SetupFocus::Field(row, SetupColumn::Name) => self.name_vec.get_mut(row),
+            SetupFocus::Field(row, SetupColumn::Result) => self.result_vec.get_mut(row),
+            SetupFocus::Start => None,
+        }
+    }
+
+    fn focus_prev(&mut self) {
+        let last_row = self.number_of_rows() - 1;
+
+        self.focus = match self.focus {
+            SetupFocus::Start => SetupFocus::Field(last_row, SetupColumn::Name),
+            SetupFocus::Field(0, _) => SetupFocus::Start,
+            SetupFocus::Field(row, column) => SetupFocus::Field(row - 1, column),
+        };
+    }
+
+    fn focus_next(&mut self) {
+        let last_row = self.number_of_rows() - 1;
+
+        self.focus = match self.focus {
+            SetupFocus::Start => SetupFocus::Field(0, SetupColumn::Name),
+            SetupFocus::Field(row, _) if row == last_row => SetupFocus::Start,
+            SetupFocus::Field(row, column) => SetupFocus::Field(row + 1, column),
+        };
+    }
+
+    fn insert_row(&mut self, row: usize, column: SetupColumn) {
+        if self.number_of_rows() >= MAX_NUMBER_OF_BLOCKS as usize {
+            return;
+        }
+
+        self.name_vec.insert(row, String::new());
+        self.result_vec.insert(row, String::new());
+        self.focus = SetupFocus::Field(row, column);
+    }
+
+    fn remove_row(&mut self) {
+        if let SetupFocus::Field(row, column) = self.focus {
+            // keep one row at least, so that there is always a field to type in
+            if self.number_of_rows() <= 1 {
+                return;
+            }
+
+            self.name_vec.remove(row);
+            self.result_vec.remove(row);
+
+            let row = row.min(self.number_of_rows() - 1);
+            self.focus = SetupFocus::Field(row, column);
+        }
+    }
+
+    fn move_field(&mut self, offset: i32) {
+        if let SetupFocus::Field(row, column) = self.focus {
+            let target = row as i32 + offset;
+            if target < 0 || target >= self.number_of_rows() as i32 {
+                return;
+            }
+
+            let target = target as usize;
+            match column {
+                SetupColumn::Name => self.name_vec.swap(row, target),
+                SetupColumn::Result => self.result_vec.swap(row, target),
+            };
+            self.focus = SetupFocus::Field(target, column);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditTarget {
+    Name,
+    Result,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EditAction {
+    Stay,
+    Apply,
+    Cancel,
+}
+
+/// State of editing a single name or result box in the sadari screen
+#[derive(Debug)]
+pub struct EditState {
+    pub target: EditTarget,
+    pub index: u8,
+    pub text: String,
+}
+
+impl EditState {
+    pub fn new(target: EditTarget, index: u8, sadari_env: &SadariEnvironment) -> EditState {
+        let vec = match target {
+            EditTarget::Name => &sadari_env.name_vec,
+            EditTarget::Result => &sadari_env.result_vec,
+        };
+
+        EditState {
+            target,
+            index,
+            text: vec.get(index as usize).cloned().unwrap_or_default(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> EditAction {
+        match key {
+            Key::Esc | Key::Ctrl('c') => EditAction::Cancel,
+            Key::Char('\n') if !self.text.trim().is_empty() => EditAction::Apply,
+            Key::Backspace => {
+                self.text.pop();
+                EditAction::Stay
+            }
+            Key::Char(c) if !c.is_control() => {
+                self.text.push(c);
+                EditAction::Stay
+            }
+            _ => EditAction::Stay,
+        }
+    }
+
+    pub fn apply(&self, sadari_env: &mut SadariEnvironment) {
+        let vec = match self.target {
+            EditTarget::Name => &mut sadari_env.name_vec,
+            EditTarget::Result => &mut sadari_env.result_vec,
+        };
+
+        if let Some(field) = vec.get_mut(self.index as usize) {
+            *field = String::from(self.text.trim());
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::helper::{calc_pairings, Point};
+use std::collections::HashMap;
+
+/// Results of `number_of_players` players split into `number_of_teams` teams,
+/// sizes of the teams differ by one at most
+pub fn calc_team_labels(number_of_players: usize, number_of_teams: u8) -> Vec<String> {
+    (0..number_of_players)
+        .map(|i| format!("Team {}", i % number_of_teams.max(1) as usize + 1))
+        .collect()
+}
+
+/// Players of each result in the order results first appear, players in the
+/// order of names
+pub fn calc_teams(
+    path_hashmap: &HashMap<u8, Vec<Point>>,
+    name_vec: &[String],
+    result_vec: &[String],
+) -> Vec<(String, Vec<String>)> {
+    let mut teams: Vec<(String, Vec<String>)> = Vec::new();
+    for result in result_vec {
+        if !teams.iter().any(|(team, _)| team == result) {
+            teams.push((result.clone(), Vec::new()));
+        }
+    }
+
+    for (name_index, result_index) in calc_pairings(path_hashmap) {
+        let result = &result_vec[result_index as usize];
+        if let Some((_, members)) = teams.iter_mut().find(|(team, _)| team == result) {
+            members.push(name_vec[name_index as usize].clone());
+        }
+    }
+
+    teams
+}
+
+/// One line per team, e.g. `Team 1: Alice, Bob`
+pub fn format_teams(teams: &[(String, Vec<String>)]) -> String {
+    teams
+        .iter()
+        .map(|(team, members)| format!("{}: {}", team, members.join(", ")))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
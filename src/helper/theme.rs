@@ -0,0 +1,302 @@
+use std::{env, fs, io};
+use tui::style::{Color, Modifier, Style};
+
+/// Styles of every part of the screens
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// border of the selected name box, focused field in the setup screen
+    pub selected: Style,
+    /// border of boxes which are not selected
+    pub border: Style,
+    /// border of the box being edited
+    pub editing: Style,
+    pub title: Style,
+    pub rail: Style,
+    pub bridge: Style,
+    pub path: Style,
+    /// border of the result box reached by the path
+    pub result: Style,
+    pub footer: Style,
+    /// pairing highlighted in the result page
+    pub highlight: Style,
+    pub ok: Style,
+    pub warning: Style,
+    pub disabled: Style,
+}
+
+const THEME_NAMES: [&str; 5] = [
+    "dark",
+    "light",
+    "high-contrast",
+    "colourblind-safe",
+    "no-color",
+];
+
+const COLORS: [(Color, &str); 17] = [
+    (Color::Reset, "reset"),
+    (Color::Black, "black"),
+    (Color::Red, "red"),
+    (Color::Green, "green"),
+    (Color::Yellow, "yellow"),
+    (Color::Blue, "blue"),
+    (Color::Magenta, "magenta"),
+    (Color::Cyan, "cyan"),
+    (Color::Gray, "gray"),
+    (Color::DarkGray, "dark_gray"),
+    (Color::LightRed, "light_red"),
+    (Color::LightGreen, "light_green"),
+    (Color::LightYellow, "light_yellow"),
+    (Color::LightBlue, "light_blue"),
+    (Color::LightMagenta, "light_magenta"),
+    (Color::LightCyan, "light_cyan"),
+    (Color::White, "white"),
+];
+
+const MODIFIERS: [(Modifier, &str); 6] = [
+    (Modifier::BOLD, "bold"),
+    (Modifier::DIM, "dim"),
+    (Modifier::ITALIC, "italic"),
+    (Modifier::UNDERLINED, "underlined"),
+    (Modifier::REVERSED, "reversed"),
+    (Modifier::CROSSED_OUT, "crossed_out"),
+];
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn bold(color: Color) -> Style {
+    Style::default().fg(color).modifier(Modifier::BOLD)
+}
+
+fn modifier(modifier: Modifier) -> Style {
+    Style::default().modifier(modifier)
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            selected: fg(Color::Red),
+            border: fg(Color::White),
+            editing: fg(Color::Green),
+            title: bold(Color::Green),
+            rail: fg(Color::LightBlue),
+            bridge: fg(Color::Yellow),
+            path: fg(Color::Red),
+            result: fg(Color::Red),
+            footer: fg(Color::Yellow),
+            highlight: bold(Color::Red),
+            ok: fg(Color::Green),
+            warning: fg(Color::Yellow),
+            disabled: fg(Color::DarkGray),
+        }
+    }
+
+    pub fn light() -> Theme {
+        Theme {
+            selected: fg(Color::Red),
+            border: fg(Color::Black),
+            editing: fg(Color::Blue),
+            title: bold(Color::Blue),
+            rail: fg(Color::Blue),
+            bridge: fg(Color::Magenta),
+            path: fg(Color::Red),
+            result: fg(Color::Red),
+            footer: fg(Color::Magenta),
+            highlight: bold(Color::Red),
+            ok: fg(Color::Green),
+            warning: fg(Color::Magenta),
+            disabled: fg(Color::Gray),
+        }
+    }
+
+    pub fn high_contrast() -> Theme {
+        Theme {
+            selected: bold(Color::LightYellow),
+            border: fg(Color::White),
+            editing: bold(Color::LightCyan),
+            title: bold(Color::White),
+            rail: fg(Color::White),
+            bridge: fg(Color::LightCyan),
+            path: bold(Color::LightYellow),
+            result: bold(Color::LightYellow),
+            footer: fg(Color::White),
+            highlight: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .modifier(Modifier::BOLD),
+            ok: bold(Color::LightGreen),
+            warning: bold(Color::LightYellow),
+            disabled: fg(Color::Gray),
+        }
+    }
+
+    /// Colours from the Okabe-Ito palette, which are distinguishable with
+    /// any type of colour blindness
+    pub fn colourblind_safe() -> Theme {
+        let orange = Color::Indexed(214);
+        let sky_blue = Color::Indexed(74);
+        let blue = Color::Indexed(32);
+        let yellow = Color::Indexed(227);
+        let purple = Color::Indexed(175);
+
+        Theme {
+            selected: bold(orange),
+            border: fg(Color::White),
+            editing: bold(purple),
+            title: bold(sky_blue),
+            rail: fg(blue),
+            bridge: fg(sky_blue),
+            path: bold(orange),
+            result: bold(orange),
+            footer: fg(yellow),
+            highlight: bold(orange),
+            ok: fg(sky_blue),
+            warning: fg(yellow),
+            disabled: fg(Color::DarkGray),
+        }
+    }
+
+    /// Modifiers only, for `NO_COLOR`
+    pub fn no_color() -> Theme {
+        Theme {
+            selected: modifier(Modifier::BOLD | Modifier::REVERSED),
+            border: Style::default(),
+            editing: modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            title: modifier(Modifier::BOLD),
+            rail: Style::default(),
+            bridge: Style::default(),
+            path: modifier(Modifier::BOLD | Modifier::REVERSED),
+            result: modifier(Modifier::BOLD | Modifier::REVERSED),
+            footer: Style::default(),
+            highlight: modifier(Modifier::REVERSED),
+            ok: modifier(Modifier::BOLD),
+            warning: modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            disabled: modifier(Modifier::DIM),
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "colourblind-safe" | "colorblind-safe" => Some(Theme::colourblind_safe()),
+            "no-color" => Some(Theme::no_color()),
+            _ => None,
+        }
+    }
+
+    pub fn names() -> &'static [&'static str] {
+        &THEME_NAMES
+    }
+
+    /// `NO_COLOR` is honoured when it is set to a non-empty value
+    pub fn from_env() -> Theme {
+        match env::var_os("NO_COLOR") {
+            Some(value) if !value.is_empty() => Theme::no_color(),
+            _ => Theme::default(),
+        }
+    }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        let style = match name {
+            "selected" => &mut self.selected,
+            "border" => &mut self.border,
+            "editing" => &mut self.editing,
+            "title" => &mut self.title,
+            "rail" => &mut self.rail,
+            "bridge" => &mut self.bridge,
+            "path" => &mut self.path,
+            "result" => &mut self.result,
+            "footer" => &mut self.footer,
+            "highlight" => &mut self.highlight,
+            "ok" => &mut self.ok,
+            "warning" => &mut self.warning,
+            "disabled" => &mut self.disabled,
+            _ => return None,
+        };
+
+        Some(style)
+    }
+
+    /// Reads styles over `self`. Each line is `part = style`, e.g.
+    /// `path = light_red on_black bold`, and `base = <theme name>` replaces
+    /// every part with the named theme.
+    pub fn parse(mut self, text: &str) -> Result<Theme, String> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.splitn(2, '=');
+            let (name, value) = match (split.next(), split.next()) {
+                (Some(name), Some(value)) => (name.trim(), value.trim()),
+                _ => return Err(format!("line {}: expected `part = style`", i + 1)),
+            };
+
+            if name == "base" {
+                self = Theme::by_name(value)
+                    .ok_or_else(|| format!("line {}: unknown theme {}", i + 1, value))?;
+                continue;
+            }
+
+            let style = parse_style(value).map_err(|err| format!("line {}: {}", i + 1, err))?;
+            let target = self
+                .style_mut(name)
+                .ok_or_else(|| format!("line {}: unknown part {}", i + 1, name))?;
+            *target = style;
+        }
+
+        Ok(self)
+    }
+}
+
+/// Style from space separated words, a colour is foreground, `on_<colour>`
+/// is background, and the others are modifiers
+pub fn parse_style(text: &str) -> Result<Style, String> {
+    text.split_whitespace()
+        .try_fold(Style::default(), |style, word| {
+            if let Some(color) = word.strip_prefix("on_").and_then(parse_color) {
+                Ok(style.bg(color))
+            } else if let Some(color) = parse_color(word) {
+                Ok(style.fg(color))
+            } else if let Some((modifier, _)) = MODIFIERS.iter().find(|(_, name)| *name == word) {
+                Ok(style.modifier(style.modifier | *modifier))
+            } else {
+                Err(format!("unknown style {}", word))
+            }
+        })
+}
+
+/// Colour name, ANSI index (0-255) or `#rrggbb`
+fn parse_color(text: &str) -> Option<Color> {
+    if let Some((color, _)) = COLORS.iter().find(|(_, name)| *name == text) {
+        return Some(*color);
+    }
+
+    if let Some(hex) = text.strip_prefix('#').filter(|hex| hex.len() == 6) {
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    text.parse::<u8>().ok().map(Color::Indexed)
+}
+
+pub fn read_theme_file(theme: Theme, filename: &str) -> Theme {
+    let text = fs::read_to_string(filename).unwrap_or_else(|err: io::Error| {
+        panic!("\n\ttheme file error : {}", err);
+    });
+
+    theme.parse(&text).unwrap_or_else(|err| {
+        panic!("\n\ttheme file error, {}", err);
+    })
+}
@@ -1,375 +1,394 @@
-use rand::rngs::ThreadRng;
+use crate::helper::{
+    calc_bridge_hashmap, calc_constrained_bridge_hashmap, calc_has_assignment, calc_max_text_width,
+    calc_positions, calc_round_results, calc_team_labels, calc_winner, read_history_file,
+    read_keymap_file, read_theme_file, validate_bridges, AssignmentRule, Geometry, History, Keymap,
+    ReportFormat, Round, Theme, CONSTRAINTS_HEADER, DEFAULT_HISTORY_FILE,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{
     collections::HashMap,
-    fmt,
+    env, fmt,
     fmt::{Debug, Display},
-    fs::File,
+    fs::{self, File},
     io,
     io::prelude::*,
     process,
 };
 
-const MAX_NUMBER_OF_BLOCKS: i32 = 12;
-const MIN_NUMBER_OF_BLOCKS: i32 = 2;
+pub const MAX_NUMBER_OF_BLOCKS: i32 = 12;
+pub const MIN_NUMBER_OF_BLOCKS: i32 = 2;
 const NUMBER_OF_LINES_TO_READ: i32 = 2;
+/// Frames per second above are clamped, frames are drawn at most every 4ms
+pub const MAX_FPS: u64 = 240;
+/// Options which do not take a value
+const FLAGS: [&str; 11] = [
+    "--ascii",
+    "--unicode",
+    "--accessible",
+    "--secret-santa",
+    "--headless",
+    "--json",
+    "--tournament",
+    "--draft",
+    "--snake",
+    "--share-rows",
+    "--no-edge-rows",
+];
 
 #[derive(Debug)]
 pub struct SadariEnvironment {
     pub number_of_blocks: u8,
-    pub number_of_max_bridges: u8,
-    pub y_coordinate: u16,
-    rng: ThreadRng,
+    pub geometry: Geometry,
+    /// ladders are reproducible with the same seed
+    pub seed: Option<u64>,
     pub name_vec: Vec<String>,
     pub result_vec: Vec<String>,
     pub tick_rate: u64,
+    pub frame_rate: u64,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// draw with ASCII characters only, instead of box-drawing characters
+    pub ascii: bool,
+    /// plain sequential text for screen readers, instead of the ladder screen
+    pub accessible: bool,
+    /// report written when the app ends
+    pub report: Option<ReportFormat>,
+    pub report_file: Option<String>,
+    /// results are the players themselves, and nobody gets themselves
+    pub secret_santa: bool,
+    /// pairs of players who should not get each other in the secret santa mode
+    pub forbidden_pairs: Vec<(String, String)>,
+    /// results are labels of this number of teams
+    pub teams: Option<u8>,
+    /// print the result without the terminal screen
+    pub headless: bool,
+    /// print the result as JSON, implies `headless`
+    pub json: bool,
+    /// half of the players advance in every round until one winner remains
+    pub tournament: bool,
+    /// number of the current round of the tournament, starts from 1
+    pub round: u8,
+    /// finished rounds of the tournament
+    pub rounds: Vec<Round>,
+    /// results are positions of the draft order
+    pub draft: bool,
+    /// second round of the draft in the reverse order, implies `draft`
+    pub snake: bool,
+    /// forbidden and required results of names
+    pub constraints: Vec<AssignmentRule>,
+    /// results of this number of last draws in the history are avoided
+    pub avoid_recent: usize,
+    /// file of past draws, the final draw is appended to it
+    pub history_file: Option<String>,
+    pub history: History,
+    /// last draws actually avoided, fewer than `avoid_recent` if the
+    /// constraints leave no assignment otherwise
+    pub avoided_draws: usize,
+    /// shown when recent results are not fully avoided
+    pub avoidance_note: Option<String>,
+    /// script of keys and waits played instead of the terminal input
+    pub replay: Option<String>,
 }
 
 impl SadariEnvironment {
     fn default() -> SadariEnvironment {
         SadariEnvironment {
             number_of_blocks: 0,
-            number_of_max_bridges: 6,
-            y_coordinate: 10,
-            rng: rand::thread_rng(),
+            geometry: Geometry::default(),
+            seed: None,
             name_vec: Vec::new(),
             result_vec: Vec::new(),
             tick_rate: 250,
+            frame_rate: 60,
+            keymap: Keymap::default(),
+            theme: Theme::from_env(),
+            ascii: !is_utf8_locale(locale().as_deref()),
+            accessible: false,
+            report: None,
+            report_file: None,
+            secret_santa: false,
+            forbidden_pairs: Vec::new(),
+            teams: None,
+            headless: false,
+            json: false,
+            tournament: false,
+            round: 1,
+            rounds: Vec::new(),
+            draft: false,
+            snake: false,
+            constraints: Vec::new(),
+            avoid_recent: 0,
+            history_file: None,
+            history: History::default(),
+            avoided_draws: 0,
+            avoidance_note: None,
+            replay: None,
         }
     }
 
-    fn number_of_blocks(mut self, number_of_blocks: u8) -> Self {
+    pub fn number_of_blocks(mut self, number_of_blocks: u8) -> Self {
         self.number_of_blocks = number_of_blocks;
 
         self
     }
 
-    fn name_vec(mut self, name_vec: Vec<String>) -> Self {
+    pub fn name_vec(mut self, name_vec: Vec<String>) -> Self {
         self.name_vec = name_vec;
 
         self
     }
 
-    fn result_vec(mut self, result_vec: Vec<String>) -> Self {
+    pub fn result_vec(mut self, result_vec: Vec<String>) -> Self {
         self.result_vec = result_vec;
 
         self
     }
-}
 
-impl Display for SadariEnvironment {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "
-        sadari env, block : {}, \
-        max_bridges : {}, \
-        y_coordinate : {}, \
-        \nname_vec : {:?}, \
-        \nresult_vec : {:?}",
-            self.number_of_blocks,
-            self.number_of_max_bridges,
-            self.y_coordinate,
-            self.name_vec,
-            self.result_vec
-        )
+    /// Random number generator of ladders, seeded by `--seed` if given
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
     }
-}
 
-mod interaction {
-    use super::{SadariEnvironment, MAX_NUMBER_OF_BLOCKS, MIN_NUMBER_OF_BLOCKS};
-    use std::io;
-    use std::io::prelude::*;
-
-    pub enum State {
-        Idle,
-        NameInput,
-        ResultInput,
-        BeforeDone,
-        Done,
-        Quit,
+    /// Results of `name_vec` given by the mode, the players in the secret
+    /// santa mode, team labels in the team mode, `advance` or `out` in the
+    /// tournament and positions in the draft mode
+    pub fn mode_results(&self, name_vec: &[String]) -> Option<Vec<String>> {
+        if self.secret_santa {
+            Some(name_vec.to_vec())
+        } else if self.tournament {
+            Some(calc_round_results(name_vec.len()))
+        } else if self.draft {
+            Some(calc_positions(name_vec.len()))
+        } else {
+            self.teams
+                .map(|teams| calc_team_labels(name_vec.len(), teams))
+        }
     }
 
-    impl State {
-        fn next_state(self) -> State {
-            match self {
-                State::Idle => State::NameInput,
-                State::NameInput => State::ResultInput,
-                State::ResultInput => State::BeforeDone,
-                State::BeforeDone => State::Done,
-                _ => self,
-            }
+    pub fn fill_mode_results(mut self) -> Self {
+        if let Some(result_vec) = self.mode_results(&self.name_vec) {
+            self.result_vec = result_vec;
         }
 
-        fn previous_state(self) -> State {
-            match self {
-                State::NameInput => State::Idle,
-                State::ResultInput => State::NameInput,
-                State::BeforeDone => State::ResultInput,
-                _ => self,
-            }
-        }
+        self
     }
 
-    pub fn help_guide() {
-        let text = r#"
-        There are TWO modes to run sadari application.
-        1. Using file path as input
-        2. Interacting with user by asking sevearl Questions.
-
-        1 -> For file as input mode example : cargo run ./text.txt
-        2 -> For interaction mode example : cargo run
+    /// Environment of the next round with the players who advanced in `round`
+    pub fn next_round(mut self, round: Round) -> SadariEnvironment {
+        let name_vec = round.advanced.clone();
+        self.rounds.push(round);
 
-        Enjoy!
-        "#;
-
-        println!("{}", text);
+        SadariEnvironment {
+            number_of_blocks: name_vec.len() as u8,
+            name_vec,
+            result_vec: Vec::new(),
+            round: self.round + 1,
+            ..self
+        }
+        .fill_mode_results()
+        .fit_avoidance()
     }
 
-    fn idle_guide() {
-        println!("\tType list of names separated by comma! ex) name1, name2, name3 ...\n");
-        println!("\tQ,q) Quit\n");
-        print!("type: ");
-
-        io::stdout().flush().unwrap();
+    /// Winner of the tournament once the final round is finished
+    pub fn winner(&self) -> Option<&String> {
+        calc_winner(&self.rounds)
     }
 
-    fn name_input_guide(v: &Vec<String>) {
-        println!("\tIs that right? \n\tnames: {:?}, len: {}\n", v, v.len());
-        println!("\tY, y) yes");
-        println!("\tN, n) no");
-        println!("\tQ,q) Quit\n");
-        print!("type: ");
-        io::stdout().flush().unwrap();
-    }
+    /// `allowed[name_index][result_index]` of the mode and the constraints,
+    /// `None` if any result is allowed
+    pub fn allowed_results(&self) -> Option<Vec<Vec<bool>>> {
+        if !self.secret_santa && self.constraints.is_empty() && self.avoided_draws == 0 {
+            return None;
+        }
 
-    fn result_input_guide() {
-        println!("\tType list of results separated by comma! ex) result1, result2, result3 ...");
-        println!("\tR, r) If you want auto generated results");
-        println!("\tQ,q) Quit\n");
-        print!("type: ");
-        io::stdout().flush().unwrap();
-    }
+        let is_forbidden = |name: &String, result: &String| {
+            self.forbidden_pairs
+                .iter()
+                .any(|(a, b)| (a == name && b == result) || (a == result && b == name))
+        };
 
-    fn before_done_guide(sadari_env: &SadariEnvironment) {
-        println!(
-            "\tIs that right? \n\tname: {:?}, len: {}",
-            sadari_env.name_vec,
-            sadari_env.name_vec.len()
-        );
-        println!(
-            "\tresult: {:?}, len: {}\n",
-            sadari_env.result_vec,
-            sadari_env.result_vec.len()
-        );
-        println!("\tY, y) yes");
-        println!("\tN, n) no");
-        println!("\tQ,q) Quit\n");
-        print!("type: ");
-        io::stdout().flush().unwrap();
-    }
+        let allowed = self
+            .name_vec
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                self.result_vec
+                    .iter()
+                    .enumerate()
+                    .map(|(j, result)| {
+                        let by_mode = !self.secret_santa || (i != j && !is_forbidden(name, result));
+                        by_mode
+                            && self
+                                .constraints
+                                .iter()
+                                .all(|constraint| constraint.allows(name, result))
+                            && !self.history.is_recent(self.avoided_draws, name, result)
+                    })
+                    .collect()
+            })
+            .collect();
 
-    pub fn handle_state_guide(state: &State, sadari_env: &SadariEnvironment) {
-        match state {
-            State::Idle => idle_guide(),
-            State::NameInput => name_input_guide(&sadari_env.name_vec),
-            State::ResultInput => result_input_guide(),
-            State::BeforeDone => before_done_guide(&sadari_env),
-            _ => {}
-        };
+        Some(allowed)
     }
 
-    pub fn handle_user_input(
-        action: String,
-        state: State,
-        sadari_env: SadariEnvironment,
-    ) -> (SadariEnvironment, State) {
-        let action = action.trim();
-
-        let mut next_sadari_env = sadari_env;
-
-        match action {
-            "Q" | "q" => (next_sadari_env, State::Quit),
-            "Y" | "y" => {
-                let (is_valid, message) = validate_input(&state, &next_sadari_env);
-
-                match message {
-                    Some(m) => println!("{}", m),
-                    None => {}
-                };
-
-                let next_state = match state {
-                    State::NameInput => {
-                        if is_valid {
-                            state.next_state()
-                        } else {
-                            state
-                        }
-                    }
-                    State::BeforeDone => {
-                        if is_valid {
-                            state.next_state()
-                        } else {
-                            state.previous_state()
-                        }
-                    }
-                    _ => state,
-                };
-
-                (next_sadari_env, next_state)
-            }
-            "N" | "n" => {
-                let next_state = match state {
-                    State::NameInput => {
-                        next_sadari_env = next_sadari_env.name_vec(Vec::new()).number_of_blocks(0);
-
-                        state.previous_state()
-                    }
-                    State::BeforeDone => {
-                        next_sadari_env = next_sadari_env.result_vec(Vec::new());
-
-                        state.previous_state()
-                    }
-                    _ => state,
-                };
-
-                (next_sadari_env, next_state)
-            }
-            "R" | "r" => {
-                let next_state = match state {
-                    State::ResultInput => {
-                        let vec: Vec<String> = (0..next_sadari_env.number_of_blocks as u8)
-                            .into_iter()
-                            .map(|x| x.to_string())
-                            .collect();
-
-                        next_sadari_env = next_sadari_env.result_vec(vec);
-
-                        state.next_state()
-                    }
-                    _ => state,
-                };
-
-                (next_sadari_env, next_state)
+    /// `Err` if a constraint has an unknown name or result, or nobody can get
+    /// an allowed result
+    pub fn check_constraints(&self) -> Result<(), String> {
+        for constraint in self.constraints.iter() {
+            if !self.name_vec.contains(constraint.name()) {
+                return Err(format!(
+                    "constraint `{}` has an unknown name: {}",
+                    constraint,
+                    constraint.name()
+                ));
             }
-            _ => {
-                let vec: Vec<String> = action
-                    .split(",")
-                    .map(move |x| String::from(x.trim()))
-                    .collect();
-
-                let next_state = match state {
-                    State::Idle => {
-                        next_sadari_env = next_sadari_env
-                            .number_of_blocks(vec.len() as u8)
-                            .name_vec(vec);
-
-                        state.next_state()
-                    }
-                    State::ResultInput => {
-                        next_sadari_env = next_sadari_env.result_vec(vec);
-
-                        let (is_valid, message) = validate_input(&state, &next_sadari_env);
-
-                        match message {
-                            Some(m) => println!("{}", m),
-                            None => {}
-                        };
-
-                        if is_valid {
-                            state.next_state()
-                        } else {
-                            next_sadari_env = next_sadari_env.result_vec(Vec::new());
-
-                            state
-                        }
-                    }
-                    _ => state,
-                };
-
-                (next_sadari_env, next_state)
+            if !self.result_vec.contains(constraint.result()) {
+                return Err(format!(
+                    "constraint `{}` has an unknown result: {}",
+                    constraint,
+                    constraint.result()
+                ));
             }
         }
-    }
 
-    fn validate_input(state: &State, sadari_env: &SadariEnvironment) -> (bool, Option<String>) {
-        match state {
-            State::NameInput => {
-                let len = sadari_env.name_vec.len();
-
-                if len >= MIN_NUMBER_OF_BLOCKS as usize && len <= MAX_NUMBER_OF_BLOCKS as usize {
-                    (true, None)
-                } else {
-                    (
-                        false,
-                        Some(format!(
-                            "\n\tInput length should be {} <= input_length <= {}\n",
-                            MIN_NUMBER_OF_BLOCKS, MAX_NUMBER_OF_BLOCKS
-                        )),
-                    )
-                }
-            }
-            State::BeforeDone | State::ResultInput => {
-                let name_vec_len = sadari_env.name_vec.len();
-                let result_vec_len = sadari_env.result_vec.len();
-
-                if name_vec_len != result_vec_len {
-                    return (
-                        false,
-                        Some(format!(
-                            "\n\tLengths are different! name: {}, result: {}\n",
-                            name_vec_len, result_vec_len
-                        )),
-                    );
+        match self.allowed_results() {
+            Some(allowed) if !calc_has_assignment(&allowed) => Err(format!(
+                "no assignment satisfies the {}",
+                match (self.secret_santa, self.constraints.is_empty()) {
+                    (true, true) => "secret santa rules and forbidden pairs",
+                    (true, false) => "secret santa rules, forbidden pairs and constraints",
+                    _ => "constraints",
                 }
+            )),
+            _ => Ok(()),
+        }
+    }
 
-                if result_vec_len >= MIN_NUMBER_OF_BLOCKS as usize
-                    && result_vec_len <= MAX_NUMBER_OF_BLOCKS as usize
-                {
-                    (true, None)
-                } else {
-                    (
-                        false,
-                        Some(format!(
-                            "\n\tInput length should be {} <= input_length <= {}\n",
-                            MIN_NUMBER_OF_BLOCKS, MAX_NUMBER_OF_BLOCKS
-                        )),
-                    )
-                }
+    /// Avoids results of as many last draws as possible, up to `avoid_recent`
+    pub fn fit_avoidance(mut self) -> Self {
+        let mut avoided_draws = self.avoid_recent;
+        while avoided_draws > 0 {
+            self.avoided_draws = avoided_draws;
+            if self
+                .allowed_results()
+                .map_or(true, |allowed| calc_has_assignment(&allowed))
+            {
+                break;
             }
-            _ => (false, None),
+            avoided_draws -= 1;
         }
+        self.avoided_draws = avoided_draws;
+
+        self.avoidance_note = if avoided_draws < self.avoid_recent.min(self.history.draws.len()) {
+            Some(match avoided_draws {
+                0 => String::from("Recent results could not be avoided"),
+                n => format!(
+                    "Results of only the last {} of {} draws could be avoided",
+                    n, self.avoid_recent
+                ),
+            })
+        } else {
+            None
+        };
+
+        self
     }
-}
 
-fn read_args_from_stdin() -> SadariEnvironment {
-    let mut sadari_env = SadariEnvironment::default();
-    let mut state = interaction::State::Idle;
+    /// Random ladder satisfying the rules of the mode, checked by
+    /// `validate_bridges`
+    pub fn bridge_hashmap<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<HashMap<u16, Vec<u16>>, String> {
+        let bridge_hashmap = match self.allowed_results() {
+            Some(allowed) => calc_constrained_bridge_hashmap(
+                self.number_of_blocks,
+                &self.geometry,
+                rng,
+                &allowed,
+            )?,
+            None => calc_bridge_hashmap(self.number_of_blocks, &self.geometry, rng),
+        };
 
-    loop {
-        interaction::handle_state_guide(&state, &sadari_env);
+        validate_bridges(&bridge_hashmap, self.number_of_blocks, &self.geometry)?;
+        Ok(bridge_hashmap)
+    }
 
-        let mut action = String::new();
-        io::stdin().read_line(&mut action).expect("read error");
+    /// Display width of the widest name or result
+    pub fn text_width(&self) -> u16 {
+        calc_max_text_width(self.name_vec.iter().chain(self.result_vec.iter()))
+    }
+}
 
-        // println!("\naction is {}\n", action);
+impl Display for SadariEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "
+        sadari env, block : {}, \
+        bridges : {}..={}, \
+        rows : {}, \
+        \nname_vec : {:?}, \
+        \nresult_vec : {:?}",
+            self.number_of_blocks,
+            self.geometry.min_bridges,
+            self.geometry.max_bridges,
+            self.geometry.rows,
+            self.name_vec,
+            self.result_vec
+        )
+    }
+}
 
-        let (next_sadari_env, next_state) =
-            interaction::handle_user_input(action, state, sadari_env);
-        state = next_state;
-        sadari_env = next_sadari_env;
+fn help_guide() {
+    let text = r#"
+        There are TWO modes to run sadari application.
+        1. Using file path as input
+        2. Filling names and results in the setup screen.
 
-        match state {
-            interaction::State::Quit => {
-                process::exit(0);
-            }
-            interaction::State::Done => {
-                break;
-            }
-            _ => {}
-        }
-    }
+        1 -> For file as input mode example : cargo run ./text.txt
+        2 -> For setup screen mode example : cargo run
+
+        After names and results, the file can have a `[constraints]` section,
+        each line is `name != result` (forbidden) or `name = result` (required).
+
+        Options
+        --fps <number>      : Frames per second of the path animation (default 60, at most 240)
+        --keymap <file>     : Key bindings, each line is `action = key, key`
+        --theme <name>      : dark, light, high-contrast, colourblind-safe or no-color
+        --theme-file <file> : Styles over the theme, each line is `part = style`
+        --ascii             : Draw with ASCII characters only (default if a set locale is not UTF-8)
+        --unicode           : Draw with box-drawing characters
+        --accessible        : Describe every path in plain text for screen readers
+        --seed <number>     : Seed of the ladder, the same seed draws the same ladder
+        --report <format>   : Write a report at the end, markdown, plain or csv
+        --report-file <file>: File of the report (default sadari-report.<md|txt|csv>)
+        --secret-santa      : Results are the players, and nobody gets themselves
+        --forbid-pair <a,b> : In the secret santa mode, a and b do not get each other
+        --constraint <rule> : `name != result` or `name = result`, like the constraints section
+        --avoid-recent <k>  : Avoid results players had in the last k draws of the history
+        --history <file>    : History of draws, appended at the end (default sadari-history.txt)
+        --teams <number>    : Split the players into balanced teams
+        --headless          : Print the result without the ladder screen
+        --json              : Print the result as JSON without the ladder screen
+        --tournament        : Half of the players advance in every round until one wins
+        --draft             : Results are positions of the draft order
+        --snake             : Draft with the second round in the reverse order
+        --rows <number>     : Rows of the ladder (default 10)
+        --min-bridges <n>   : Fewest bridges between two rails (default 2)
+        --max-bridges <n>   : Most bridges between two rails (default 5)
+        --density <percent> : Percentage of all rows having a bridge, within min and max bridges
+        --share-rows        : Bridges of neighbouring rails can be in the same row
+        --no-edge-rows      : No bridge in the very top and bottom rows
+        --replay <file>     : Play keys and waits of a script instead of reading the terminal
 
-    sadari_env
+        Enjoy!
+        "#;
+
+    println!("{}", text);
 }
 
 fn get_input_from_file(filename: &String) -> Result<Vec<Vec<String>>, io::Error> {
@@ -377,37 +396,52 @@ fn get_input_from_file(filename: &String) -> Result<Vec<Vec<String>>, io::Error>
     let reader = std::io::BufReader::new(&file);
 
     let mut vec: Vec<Vec<String>> = Vec::new();
-    let mut line_iter = reader.lines();
+    // constraints section is not a line of names or results
+    let mut line_iter = reader
+        .lines()
+        .map(|l| l.unwrap())
+        .take_while(|l| l.trim() != CONSTRAINTS_HEADER);
 
-    (0..NUMBER_OF_LINES_TO_READ).into_iter().for_each(|_| {
+    (0..NUMBER_OF_LINES_TO_READ).for_each(|_| {
         let line = line_iter.next();
 
-        match line {
-            Some(l) => {
-                let s: String = l.unwrap();
-                let v: Vec<String> = s.split(",").map(move |x| String::from(x.trim())).collect();
-                vec.push(v);
-            }
-            None => {}
+        if let Some(s) = line {
+            let v: Vec<String> = s.split(",").map(move |x| String::from(x.trim())).collect();
+            vec.push(v);
         };
     });
 
     Ok(vec)
 }
 
-fn read_args_from_file(args: Vec<String>) -> SadariEnvironment {
-    let filename = &args[1];
+/// Lines after `[constraints]` in the input file, empty lines and lines
+/// starting with `#` are skipped
+fn get_constraints_from_file(filename: &String) -> Result<Vec<AssignmentRule>, String> {
+    let text = fs::read_to_string(filename).map_err(|err| err.to_string())?;
+
+    text.lines()
+        .skip_while(|line| line.trim() != CONSTRAINTS_HEADER)
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+        .map(|(i, line)| {
+            AssignmentRule::parse(line).map_err(|err| format!("constraints line {}: {}", i, err))
+        })
+        .collect()
+}
+
+fn read_args_from_file(filename: &String) -> SadariEnvironment {
     let vec_read_file = get_input_from_file(filename).unwrap_or_else(|err| {
         panic!("\n\tget_input_from_file error : {}", err);
     });
 
-    if vec_read_file.len() < 1 {
+    if vec_read_file.is_empty() {
         panic!("\n\ttest input file has few lines, provide 2 lines!");
     }
 
     let name_vec: &Vec<String> = vec_read_file
-        .get(0)
-        .ok_or_else(|| "no input for names")
+        .first()
+        .ok_or("no input for names")
         .unwrap_or_else(|err| {
             panic!("\n\tname_vec, test error : {}", err);
         });
@@ -429,10 +463,7 @@ fn read_args_from_file(args: Vec<String>) -> SadariEnvironment {
     let name_vec = name_vec.clone();
     let result_vec: Vec<String> = if vec_read_file.len() == 1 {
         eprintln!("because i got one line, result will be automatically set as number, 0..n");
-        let vec: Vec<String> = (0..number_of_bloks as u8)
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect();
+        let vec: Vec<String> = (0..number_of_bloks as u8).map(|x| x.to_string()).collect();
 
         vec
     } else {
@@ -447,10 +478,17 @@ fn read_args_from_file(args: Vec<String>) -> SadariEnvironment {
         );
     }
 
-    SadariEnvironment::default()
+    let constraints = get_constraints_from_file(filename).unwrap_or_else(|err| {
+        panic!("\n\t{}", err);
+    });
+
+    let mut sadari_env = SadariEnvironment::default()
         .number_of_blocks(number_of_bloks as u8)
         .name_vec(name_vec)
-        .result_vec(result_vec)
+        .result_vec(result_vec);
+    sadari_env.constraints = constraints;
+
+    sadari_env
 }
 
 pub fn read_args<T>(args: T) -> SadariEnvironment
@@ -460,15 +498,241 @@ where
     let args: Vec<String> = args.collect();
 
     if args.len() >= 2 && ["help", "--help"].contains(&args[1].as_str()) {
-        interaction::help_guide();
+        help_guide();
         process::exit(0);
     }
 
-    if args.len() < 2 {
-        read_args_from_stdin()
-    } else {
-        read_args_from_file(args)
+    let mut filename: Option<String> = None;
+    let mut options: Vec<(String, String)> = Vec::new();
+
+    let mut iter = args.into_iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if FLAGS.contains(&arg.as_str()) {
+            options.push((arg, String::new()));
+        } else if arg.starts_with("--") {
+            let value = iter
+                .next()
+                .unwrap_or_else(|| panic!("\n\toption {} needs a value", arg));
+            options.push((arg, value));
+        } else if filename.is_none() {
+            filename = Some(arg);
+        }
     }
+
+    let sadari_env = match filename {
+        Some(filename) => read_args_from_file(&filename),
+        // names and results are filled later in the setup screen
+        None => SadariEnvironment::default(),
+    };
+
+    let sadari_env = options
+        .iter()
+        .fold(sadari_env, |sadari_env, (option, value)| {
+            read_option(sadari_env, option, value)
+        })
+        .fill_mode_results();
+
+    let modes = [
+        sadari_env.secret_santa,
+        sadari_env.teams.is_some(),
+        sadari_env.tournament,
+        sadari_env.draft,
+    ];
+    if modes.iter().filter(|mode| **mode).count() > 1 {
+        panic!("\n\tonly one of --secret-santa, --teams, --tournament and --draft can be used");
+    }
+
+    if let Some(teams) = sadari_env.teams {
+        if !sadari_env.name_vec.is_empty() && teams as usize > sadari_env.name_vec.len() {
+            panic!(
+                "\n\tnumber of teams is larger than players, teams: {}, players: {}",
+                teams,
+                sadari_env.name_vec.len()
+            );
+        }
+    }
+
+    if let Err(err) = sadari_env.geometry.check() {
+        panic!("\n\t{}", err);
+    }
+
+    let mut sadari_env = sadari_env;
+    if sadari_env.avoid_recent > 0 && sadari_env.history_file.is_none() {
+        sadari_env.history_file = Some(String::from(DEFAULT_HISTORY_FILE));
+    }
+    if let Some(filename) = &sadari_env.history_file {
+        sadari_env.history = read_history_file(filename);
+    }
+
+    // names and results of the setup screen are checked later
+    if !sadari_env.name_vec.is_empty() {
+        if let Err(err) = sadari_env.check_constraints() {
+            panic!("\n\t{}", err);
+        }
+        sadari_env = sadari_env.fit_avoidance();
+    }
+
+    sadari_env
+}
+
+fn read_option(mut sadari_env: SadariEnvironment, option: &str, value: &str) -> SadariEnvironment {
+    match option {
+        "--fps" => {
+            sadari_env.frame_rate = value
+                .parse::<u64>()
+                .ok()
+                .filter(|fps| *fps > 0)
+                .unwrap_or_else(|| panic!("\n\t--fps should be a positive number, got: {}", value))
+                .min(MAX_FPS);
+        }
+        "--keymap" => {
+            sadari_env.keymap = read_keymap_file(value);
+        }
+        "--replay" => {
+            sadari_env.replay = Some(String::from(value));
+        }
+        "--theme" => {
+            sadari_env.theme = Theme::by_name(value).unwrap_or_else(|| {
+                panic!(
+                    "\n\tunknown theme: {}, themes: {}",
+                    value,
+                    Theme::names().join(", ")
+                )
+            });
+        }
+        "--theme-file" => {
+            sadari_env.theme = read_theme_file(sadari_env.theme.clone(), value);
+        }
+        "--ascii" => {
+            sadari_env.ascii = true;
+        }
+        "--unicode" => {
+            sadari_env.ascii = false;
+        }
+        "--accessible" => {
+            sadari_env.accessible = true;
+        }
+        "--seed" => {
+            sadari_env.seed = Some(
+                value
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| panic!("\n\t--seed should be a number, got: {}", value)),
+            );
+        }
+        "--report" => {
+            sadari_env.report = Some(ReportFormat::by_name(value).unwrap_or_else(|| {
+                panic!(
+                    "\n\tunknown report format: {}, formats: {}",
+                    value,
+                    ReportFormat::names().join(", ")
+                )
+            }));
+        }
+        "--secret-santa" => {
+            sadari_env.secret_santa = true;
+        }
+        "--forbid-pair" => {
+            let mut split = value.splitn(2, ',');
+            let pair = match (split.next(), split.next()) {
+                (Some(a), Some(b)) => (String::from(a.trim()), String::from(b.trim())),
+                _ => panic!("\n\t--forbid-pair should be `name, name`, got: {}", value),
+            };
+            sadari_env.forbidden_pairs.push(pair);
+        }
+        "--teams" => {
+            sadari_env.teams = Some(
+                value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|teams| *teams >= 2)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "\n\t--teams should be a number of 2 or more, got: {}",
+                            value
+                        )
+                    }),
+            );
+        }
+        "--tournament" => {
+            sadari_env.tournament = true;
+        }
+        "--draft" => {
+            sadari_env.draft = true;
+        }
+        "--snake" => {
+            sadari_env.draft = true;
+            sadari_env.snake = true;
+        }
+        "--headless" => {
+            sadari_env.headless = true;
+        }
+        "--json" => {
+            sadari_env.headless = true;
+            sadari_env.json = true;
+        }
+        "--constraint" => {
+            let constraint = AssignmentRule::parse(value)
+                .unwrap_or_else(|err| panic!("\n\t--constraint {}", err));
+            sadari_env.constraints.push(constraint);
+        }
+        "--avoid-recent" => {
+            sadari_env.avoid_recent = value.parse::<usize>().unwrap_or_else(|_| {
+                panic!("\n\t--avoid-recent should be a number, got: {}", value)
+            });
+        }
+        "--history" => {
+            sadari_env.history_file = Some(String::from(value));
+        }
+        "--rows" => {
+            sadari_env.geometry.rows = parse_number(option, value);
+        }
+        "--min-bridges" => {
+            sadari_env.geometry.min_bridges = parse_number(option, value);
+        }
+        "--max-bridges" => {
+            sadari_env.geometry.max_bridges = parse_number(option, value);
+        }
+        "--density" => {
+            sadari_env.geometry.density = Some(parse_number(option, value));
+        }
+        "--share-rows" => {
+            sadari_env.geometry.share_rows = true;
+        }
+        "--no-edge-rows" => {
+            sadari_env.geometry.edge_rows = false;
+        }
+        "--report-file" => {
+            sadari_env.report_file = Some(String::from(value));
+        }
+        _ => panic!("\n\tunknown option: {}", option),
+    };
+
+    sadari_env
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> T {
+    value
+        .parse::<T>()
+        .unwrap_or_else(|_| panic!("\n\t{} should be a number, got: {}", option, value))
+}
+
+/// First non-empty one of `LC_ALL`, `LC_CTYPE` and `LANG`
+fn locale() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Whether the character set of `locale` is UTF-8. Terminals without any
+/// locale are mostly UTF-8 ones, so only a set non-UTF-8 locale is ASCII.
+pub fn is_utf8_locale(locale: Option<&str>) -> bool {
+    locale
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(true)
 }
 
 fn _print_hashmap<K, V>(name: String, hashmap: &HashMap<K, V>)
@@ -0,0 +1,113 @@
+use crate::helper::{calc_pairings, json_array, json_string, Point};
+use std::collections::HashMap;
+
+pub const ADVANCE: &str = "advance";
+pub const OUT: &str = "out";
+
+/// Results of a round, half of the players advance
+pub fn calc_round_results(number_of_players: usize) -> Vec<String> {
+    (0..number_of_players)
+        .map(|i| String::from(if i % 2 == 1 { ADVANCE } else { OUT }))
+        .collect()
+}
+
+/// Players who advanced and who are out in a round of the tournament
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round {
+    /// starts from 1
+    pub number: u8,
+    pub advanced: Vec<String>,
+    pub out: Vec<String>,
+}
+
+impl Round {
+    /// `Err` if nobody advanced, the tournament would have no winner
+    pub fn new(
+        number: u8,
+        path_hashmap: &HashMap<u8, Vec<Point>>,
+        name_vec: &[String],
+        result_vec: &[String],
+    ) -> Result<Round, String> {
+        let mut round = Round {
+            number,
+            advanced: Vec::new(),
+            out: Vec::new(),
+        };
+
+        for (name_index, result_index) in calc_pairings(path_hashmap) {
+            let name = name_vec[name_index as usize].clone();
+
+            if result_vec[result_index as usize] == ADVANCE {
+                round.advanced.push(name);
+            } else {
+                round.out.push(name);
+            }
+        }
+
+        if round.advanced.is_empty() {
+            return Err(format!("nobody advanced in round {}", number));
+        }
+
+        Ok(round)
+    }
+
+    /// The last round has one player left
+    pub fn is_final(&self) -> bool {
+        self.advanced.len() == 1
+    }
+}
+
+/// Winner of the tournament once the final round is finished
+pub fn calc_winner(rounds: &[Round]) -> Option<&String> {
+    rounds
+        .last()
+        .filter(|round| round.is_final())
+        .and_then(|round| round.advanced.first())
+}
+
+/// One line per round and the winner, e.g. `Round 1: Alice, Bob advance, Carol out`
+pub fn format_bracket(rounds: &[Round]) -> Vec<String> {
+    let mut lines: Vec<String> = rounds
+        .iter()
+        .map(|round| {
+            format!(
+                "Round {}: {} advance, {} out",
+                round.number,
+                round.advanced.join(", "),
+                round.out.join(", ")
+            )
+        })
+        .collect();
+
+    if let Some(winner) = calc_winner(rounds) {
+        lines.push(format!("Winner: {}", winner));
+    }
+
+    lines
+}
+
+/// `{"rounds":[{"round":1,"advanced":[..],"out":[..]}],"winner":..}`, the
+/// winner is `null` until the final round is finished
+pub fn format_bracket_json(rounds: &[Round]) -> String {
+    let names = |names: &[String]| json_array(names.iter().map(|name| json_string(name)).collect());
+
+    let rounds_json = rounds
+        .iter()
+        .map(|round| {
+            format!(
+                "{{\"round\":{},\"advanced\":{},\"out\":{}}}",
+                round.number,
+                names(&round.advanced),
+                names(&round.out)
+            )
+        })
+        .collect();
+    let winner =
+        calc_winner(rounds).map_or_else(|| String::from("null"), |winner| json_string(winner));
+
+    format!(
+        "{{\"rounds\":{},\"winner\":{}}}",
+        json_array(rounds_json),
+        winner
+    )
+}
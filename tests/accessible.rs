@@ -0,0 +1,97 @@
+use rust_sadari_cli::helper;
+use std::{collections::HashMap, io::Cursor};
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn calc_path_steps_should_describe_every_bridge() {
+    let name_vec = to_strings(&["Alice", "Bob", "Carol"]);
+    let result_vec = to_strings(&["Free", "Pay", "Wash"]);
+
+    // gap 0 has a bridge in row 3, gap 1 in row 6
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    bridge_hashmap.insert(1, vec![5]);
+    let path_hashmap = helper::calc_path_hashmap(3, &bridge_hashmap, 10);
+
+    assert_eq!(
+        helper::calc_path_steps(path_hashmap.get(&0).unwrap(), &name_vec, &result_vec),
+        vec![
+            "Alice starts at the top of rail 1.",
+            "Goes down to row 3 of 10, crosses right to Bob's rail.",
+            "Goes down to row 6 of 10, crosses right to Carol's rail.",
+            "Goes down to the bottom of rail 3 and gets Wash.",
+        ]
+    );
+    assert_eq!(
+        helper::calc_path_steps(path_hashmap.get(&2).unwrap(), &name_vec, &result_vec),
+        vec![
+            "Carol starts at the top of rail 3.",
+            "Goes down to row 6 of 10, crosses left to Bob's rail.",
+            "Goes down to the bottom of rail 2 and gets Pay.",
+        ]
+    );
+    assert_eq!(
+        helper::calc_result_lines(&path_hashmap, &name_vec, &result_vec),
+        vec!["Alice: Wash", "Bob: Free", "Carol: Pay"]
+    );
+}
+
+#[test]
+fn calc_path_steps_should_describe_a_crossing_of_shared_rows_once() {
+    let name_vec = to_strings(&["Alice", "Bob", "Carol"]);
+    let result_vec = to_strings(&["Free", "Pay", "Wash"]);
+
+    // both gaps share row 3, gap 1 has another bridge in row 9
+    let geometry = helper::Geometry {
+        min_bridges: 1,
+        share_rows: true,
+        ..helper::Geometry::default()
+    };
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    bridge_hashmap.insert(1, vec![2, 8]);
+    assert_eq!(
+        helper::validate_bridges(&bridge_hashmap, 3, &geometry),
+        Ok(())
+    );
+    let path_hashmap = helper::calc_path_hashmap(3, &bridge_hashmap, geometry.rows);
+
+    assert_eq!(
+        helper::calc_path_steps(path_hashmap.get(&0).unwrap(), &name_vec, &result_vec),
+        vec![
+            "Alice starts at the top of rail 1.",
+            "Goes down to row 3 of 10, crosses right to Carol's rail.",
+            "Goes down to row 9 of 10, crosses left to Bob's rail.",
+            "Goes down to the bottom of rail 2 and gets Pay.",
+        ]
+    );
+    assert_eq!(
+        helper::calc_path_steps(path_hashmap.get(&2).unwrap(), &name_vec, &result_vec),
+        vec![
+            "Carol starts at the top of rail 3.",
+            "Goes down to row 3 of 10, crosses left to Bob's rail.",
+            "Goes down to row 9 of 10, crosses right to Carol's rail.",
+            "Goes down to the bottom of rail 3 and gets Wash.",
+        ]
+    );
+}
+
+#[test]
+fn run_accessible_should_ask_again_for_invalid_input() {
+    let sadari_env = helper::read_args(vec![String::from("dummy path")].into_iter());
+    let input = Cursor::new("Alice\nFree\nAlice, Bob, Carol\nFree, Pay\nAlice, Bob\n\n");
+    let mut out = Vec::new();
+
+    helper::run_accessible(input, &mut out, sadari_env).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Number of rows should be"));
+    assert!(out.contains("There are 3 names and 2 results"));
+    assert!(out.contains("Bob starts at the top of rail 2."));
+    // results are filled with numbers
+    assert!(out.contains("Results:\nAlice: "));
+    assert_eq!(out.lines().filter(|line| line.ends_with(": 0")).count(), 1);
+}
@@ -0,0 +1,80 @@
+use rust_sadari_cli::helper::Animation;
+use std::time::Duration;
+
+#[test]
+fn animation_speed_should_not_depend_on_frame_rate() {
+    let mut fast = Animation::new();
+    let mut slow = Animation::new();
+
+    (0..120).for_each(|_| fast.advance(Duration::from_micros(1_000_000 / 120)));
+    (0..10).for_each(|_| slow.advance(Duration::from_micros(1_000_000 / 10)));
+
+    assert!((fast.tick - slow.tick).abs() <= 1);
+    assert!(fast.tick > 0);
+}
+
+#[test]
+fn animation_should_skip_and_reset() {
+    let mut animation = Animation::new();
+    animation.advance(Duration::from_millis(100));
+    animation.skip();
+    assert_eq!(animation.tick, i32::MAX);
+
+    animation.advance(Duration::from_millis(100));
+    assert_eq!(animation.tick, i32::MAX);
+
+    animation.reset();
+    assert_eq!(animation.tick, 0);
+}
+
+#[test]
+fn animation_should_not_advance_while_paused() {
+    let mut animation = Animation::new();
+    animation.toggle_pause();
+    animation.advance(Duration::from_millis(100));
+    assert_eq!(animation.tick, 0);
+
+    animation.toggle_pause();
+    animation.advance(Duration::from_millis(100));
+    assert!(animation.tick > 0);
+}
+
+#[test]
+fn animation_should_step_to_the_end_of_segments() {
+    let segment_ends = [5, 5, 12, 20];
+    let mut animation = Animation::new();
+
+    animation.step(&segment_ends);
+    assert_eq!(animation.tick, 5);
+    assert!(animation.paused);
+
+    animation.step(&segment_ends);
+    assert_eq!(animation.tick, 12);
+
+    // last segment needs one more tick to finish the path
+    animation.step(&segment_ends);
+    assert_eq!(animation.tick, 21);
+
+    animation.step(&segment_ends);
+    assert_eq!(animation.tick, 21);
+}
+
+#[test]
+fn animation_speed_should_have_bounds() {
+    let mut animation = Animation::new();
+    assert_eq!(animation.speed(), 100);
+
+    let mut normal = Animation::new();
+    animation.speed_up();
+    animation.advance(Duration::from_millis(120));
+    normal.advance(Duration::from_millis(120));
+    assert_eq!(animation.tick, normal.tick * 2);
+
+    (0..10).for_each(|_| animation.speed_up());
+    let max_speed = animation.speed();
+    (0..10).for_each(|_| animation.speed_down());
+    assert!(animation.speed() < 100 && max_speed > 100);
+
+    animation.reset();
+    assert!(animation.speed() < 100);
+}
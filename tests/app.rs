@@ -0,0 +1,368 @@
+use rust_sadari_cli::helper::{
+    self, CopyFormat, Event, EventSource, Key, Outcome, SadariEnvironment, ScriptStep,
+    ScriptedEvents, Theme,
+};
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    sync::mpsc,
+    time::Duration,
+};
+use tui::{
+    backend::{Backend, TestBackend},
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Style},
+    Terminal,
+};
+
+const DATA_PATH: &str = "tests/data";
+const FRAME_RATE: Duration = Duration::from_millis(10);
+
+/// Test backend keeping what is written to the terminal besides the cells,
+/// e.g. the clipboard sequence
+struct RecordingBackend {
+    backend: TestBackend,
+    written: Vec<u8>,
+}
+
+impl RecordingBackend {
+    fn new(width: u16, height: u16) -> RecordingBackend {
+        RecordingBackend {
+            backend: TestBackend::new(width, height),
+            written: Vec::new(),
+        }
+    }
+}
+
+impl Write for RecordingBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Backend for RecordingBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.backend.draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.backend.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.backend.show_cursor()
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        self.backend.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.backend.set_cursor(x, y)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.backend.clear()
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        self.backend.size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.backend)
+    }
+}
+
+fn data_path(path: &str) -> String {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/{}", DATA_PATH, path));
+
+    String::from(d.to_str().unwrap())
+}
+
+fn read_sadari_env(args: &[&str]) -> SadariEnvironment {
+    let mut mock_args = vec![String::from("dummy path")];
+    mock_args.extend(args.iter().map(|arg| String::from(*arg)));
+
+    let mut sadari_env = helper::read_args(mock_args.into_iter());
+    sadari_env.ascii = true;
+    // path has a colour of its own
+    sadari_env.theme = Theme {
+        path: Style::default().fg(Color::LightMagenta),
+        ..Theme::dark()
+    };
+
+    sadari_env
+}
+
+/// Runs the app with the script, returns the outcome and the last screen
+fn run_script(sadari_env: SadariEnvironment, script: &str) -> (Option<Outcome>, Buffer) {
+    let (outcome, terminal) = run_script_terminal(sadari_env, script);
+
+    (outcome, terminal.backend().backend.buffer().clone())
+}
+
+fn run_script_terminal(
+    sadari_env: SadariEnvironment,
+    script: &str,
+) -> (Option<Outcome>, Terminal<RecordingBackend>) {
+    let steps = helper::parse_script(script).unwrap();
+    let mut events = ScriptedEvents::new(steps, FRAME_RATE);
+    let mut terminal = Terminal::new(RecordingBackend::new(100, 40)).unwrap();
+
+    let outcome = helper::run_app(&mut terminal, &mut events, sadari_env).unwrap();
+
+    (outcome, terminal)
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    buffer
+        .content()
+        .chunks(buffer.area().width as usize)
+        .map(|line| line.iter().map(|cell| cell.symbol.as_str()).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn path_cells(buffer: &Buffer) -> usize {
+    buffer
+        .content()
+        .iter()
+        .filter(|cell| cell.style.fg == Color::LightMagenta)
+        .count()
+}
+
+#[test]
+fn parse_script_should_read_steps() {
+    let steps = helper::parse_script(
+        "# comment\n\nkey s ctrl+r enter\ntype a b\nwait 250\ntick\nclick 3 4\nscroll-down 0 1\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        steps,
+        vec![
+            ScriptStep::Event(Event::Input(Key::Char('s'))),
+            ScriptStep::Event(Event::Input(Key::Ctrl('r'))),
+            ScriptStep::Event(Event::Input(Key::Char('\n'))),
+            ScriptStep::Event(Event::Input(Key::Char('a'))),
+            ScriptStep::Event(Event::Input(Key::Char(' '))),
+            ScriptStep::Event(Event::Input(Key::Char('b'))),
+            ScriptStep::Wait(Duration::from_millis(250)),
+            ScriptStep::Event(Event::Tick),
+            ScriptStep::Event(Event::Mouse(helper::Mouse::Press(3, 4))),
+            ScriptStep::Event(Event::Mouse(helper::Mouse::ScrollDown(0, 1))),
+        ]
+    );
+}
+
+#[test]
+fn parse_script_should_reject_unknown_steps() {
+    assert_eq!(
+        helper::parse_script("key s\njump"),
+        Err(String::from("line 2: unknown step, got: jump"))
+    );
+    assert_eq!(
+        helper::parse_script("wait soon"),
+        Err(String::from(
+            "line 1: expected milliseconds, got: wait soon"
+        ))
+    );
+    assert_eq!(
+        helper::parse_script("click 3"),
+        Err(String::from("line 1: expected `x y`, got: click 3"))
+    );
+    assert_eq!(
+        helper::parse_script("key ctrl+shift"),
+        Err(String::from(
+            "line 1: unknown key ctrl+shift, got: key ctrl+shift"
+        ))
+    );
+}
+
+#[test]
+fn scripted_events_should_send_frames_only_while_enabled() {
+    let steps = vec![
+        ScriptStep::Wait(Duration::from_millis(35)),
+        ScriptStep::Event(Event::Tick),
+        ScriptStep::Wait(Duration::from_millis(100)),
+    ];
+    let mut events = ScriptedEvents::new(steps, FRAME_RATE);
+    let start = events.now();
+
+    events.enable_frame();
+    for _ in 0..3 {
+        assert_eq!(events.next(), Some(Event::Frame));
+    }
+    assert_eq!(events.now() - start, Duration::from_millis(30));
+
+    // the rest of the wait is shorter than a frame
+    assert_eq!(events.next(), Some(Event::Tick));
+    assert_eq!(events.now() - start, Duration::from_millis(35));
+
+    events.disable_frame();
+    assert_eq!(events.next(), None);
+    assert_eq!(events.now() - start, Duration::from_millis(135));
+}
+
+#[test]
+fn scripted_events_should_end_by_the_exit_key() {
+    let steps = vec![
+        ScriptStep::Event(Event::Tick),
+        ScriptStep::Wait(Duration::from_secs(60)),
+        ScriptStep::Event(Event::Tick),
+    ];
+    let (tx, rx) = mpsc::channel();
+    let mut events = ScriptedEvents::new(steps, FRAME_RATE)
+        .realtime()
+        .exit_key(rx);
+
+    assert_eq!(events.next(), Some(Event::Tick));
+
+    // the wait of a realtime script is stopped as well
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        tx.send(Key::Char('q')).unwrap();
+    });
+    let start = std::time::Instant::now();
+    assert_eq!(events.next(), None);
+    assert!(start.elapsed() < Duration::from_secs(10));
+    handle.join().unwrap();
+}
+
+#[test]
+fn run_app_should_play_the_replay_file() {
+    let sadari_env = read_sadari_env(&["--replay", &data_path("replay.txt")]);
+    let steps = helper::read_script_file(sadari_env.replay.as_ref().unwrap());
+    let mut events = ScriptedEvents::new(steps, FRAME_RATE);
+    let mut terminal = Terminal::new(RecordingBackend::new(100, 40)).unwrap();
+
+    let outcome = helper::run_app(&mut terminal, &mut events, sadari_env)
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome.sadari_env.name_vec, vec!["Alice", "Bob"]);
+    assert_eq!(outcome.sadari_env.result_vec, vec!["0", "1"]);
+
+    // the script ends in the result page
+    let text = buffer_text(terminal.backend().backend.buffer());
+    for (name_index, path) in outcome.path_hashmap.iter() {
+        let name = &outcome.sadari_env.name_vec[*name_index as usize];
+        let result = &outcome.sadari_env.result_vec[path.last().unwrap().x as usize];
+
+        assert!(text.lines().any(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            words.contains(&name.as_str()) && words.contains(&result.as_str())
+        }));
+    }
+}
+
+#[test]
+fn run_app_should_draw_the_path_by_the_time_of_the_script() {
+    let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+
+    // a cell of the path is drawn every 12ms at the normal speed
+    let (_, buffer) = run_script(sadari_env, "key s\nwait 120");
+    assert_eq!(path_cells(&buffer), 10);
+
+    let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+    let (_, buffer) = run_script(sadari_env, "key s\nwait 120\nkey space\nwait 500");
+    assert_eq!(path_cells(&buffer), 10);
+}
+
+#[test]
+fn run_app_should_edit_names_and_quit_by_key() {
+    let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+
+    // keys after quit are not read
+    let (outcome, _) = run_script(
+        sadari_env,
+        "key right e backspace\ntype 9 and q\nkey enter q\nkey e",
+    );
+    let outcome = outcome.unwrap();
+
+    assert_eq!(
+        outcome.sadari_env.name_vec,
+        vec!["name1", "name9 and q", "name3", "name4", "name5"]
+    );
+    assert_eq!(outcome.path_hashmap.len(), 5);
+}
+
+#[test]
+fn run_app_should_reveal_the_draw_by_the_result_page_or_every_path() {
+    let revealed = |script: &str| {
+        let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+        run_script(sadari_env, script).0.unwrap().revealed
+    };
+
+    assert!(!revealed("key q"));
+    assert!(!revealed(&"key s s\ntick\nkey right\n".repeat(4)));
+    assert!(revealed("key r r q"));
+
+    let every_path = "key s s\ntick\nkey right\n".repeat(5);
+    assert!(revealed(&every_path));
+
+    // a new ladder is not seen yet
+    assert!(!revealed(&format!("{}key n", every_path)));
+}
+
+#[test]
+fn run_app_should_not_edit_results_of_a_mode() {
+    for mode in ["--tournament", "--secret-santa", "--draft"].iter() {
+        let sadari_env = read_sadari_env(&[mode, &data_path("input_same_length.txt")]);
+        let result_vec = sadari_env.result_vec.clone();
+
+        let (outcome, _) = run_script(sadari_env, "key E backspace\ntype x\nkey enter");
+        assert_eq!(
+            outcome.unwrap().sadari_env.result_vec,
+            result_vec,
+            "{}",
+            mode
+        );
+    }
+
+    let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+    let (outcome, _) = run_script(sadari_env, "key E backspace\ntype x\nkey enter");
+    assert_eq!(outcome.unwrap().sadari_env.result_vec[0], "resultx");
+}
+
+#[test]
+fn run_app_should_quit_without_outcome_in_the_setup() {
+    let sadari_env = read_sadari_env(&[]);
+    let (outcome, buffer) = run_script(sadari_env, "type Alice\nkey esc\ntype Bob");
+
+    assert!(outcome.is_none());
+    assert!(buffer_text(&buffer).contains("Alice"));
+
+    // the end of the script quits as well
+    let sadari_env = read_sadari_env(&[]);
+    let (outcome, _) = run_script(sadari_env, "type Alice");
+    assert!(outcome.is_none());
+}
+
+#[test]
+fn run_app_should_copy_the_mapping_through_the_backend() {
+    let sadari_env = read_sadari_env(&[&data_path("input_same_length.txt")]);
+    let (outcome, terminal) = run_script_terminal(sadari_env, "key r c");
+    let outcome = outcome.unwrap();
+
+    let text = helper::format_mapping(
+        CopyFormat::Plain,
+        &outcome.path_hashmap,
+        &outcome.sadari_env.name_vec,
+        &outcome.sadari_env.result_vec,
+    );
+    let sequence = helper::osc52_sequence(&text, std::env::var_os("TMUX").is_some());
+    assert_eq!(
+        String::from_utf8(terminal.backend().written.clone()).unwrap(),
+        sequence
+    );
+}
@@ -0,0 +1,192 @@
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use rust_sadari_cli::helper::{self, Geometry};
+use std::collections::{HashMap, HashSet};
+
+fn geometry() -> impl Strategy<Value = Geometry> {
+    (
+        1..40u16,
+        0..6u8,
+        0..10u8,
+        proptest::option::of(0..=100u8),
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(rows, min_bridges, extra_bridges, density, share_rows, edge_rows)| Geometry {
+                rows,
+                min_bridges,
+                max_bridges: min_bridges + extra_bridges,
+                density,
+                share_rows,
+                edge_rows,
+            },
+        )
+        .prop_filter("geometry should be valid", |geometry| {
+            geometry.check().is_ok()
+        })
+}
+
+/// Result index of every name, panics unless every path ends at the bottom
+fn results_of(
+    number_of_blocks: u8,
+    bridge_hashmap: &HashMap<u16, Vec<u16>>,
+    rows: u16,
+) -> Vec<usize> {
+    let path_hashmap = helper::calc_path_hashmap(number_of_blocks, bridge_hashmap, rows);
+
+    (0..number_of_blocks)
+        .map(|index| {
+            let end = path_hashmap[&index].last().unwrap();
+            assert_eq!(end.y, rows as i32);
+
+            end.x as usize
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn generated_ladder_should_be_valid_permutation(
+        number_of_blocks in 2..=12u8,
+        geometry in geometry(),
+        seed in any::<u64>(),
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let bridge_hashmap = helper::calc_bridge_hashmap(number_of_blocks, &geometry, &mut rng);
+
+        prop_assert_eq!(
+            helper::validate_bridges(&bridge_hashmap, number_of_blocks, &geometry),
+            Ok(())
+        );
+        let counts = geometry.min_bridges as usize..=geometry.max_bridges as usize;
+        prop_assert!(bridge_hashmap
+            .values()
+            .all(|vec| counts.contains(&vec.len())));
+
+        let results = results_of(number_of_blocks, &bridge_hashmap, geometry.rows);
+        prop_assert_eq!(
+            results.iter().collect::<HashSet<_>>().len(),
+            number_of_blocks as usize
+        );
+    }
+
+    #[test]
+    fn arranged_ladder_should_be_valid_and_give_the_assignment(
+        assignment in Just((0..12usize).collect::<Vec<usize>>()).prop_shuffle(),
+        number_of_blocks in 2..=12u8,
+        geometry in geometry(),
+        seed in any::<u64>(),
+    ) {
+        // the first names of the shuffled results, renumbered in order
+        let mut assignment: Vec<usize> = assignment
+            .into_iter()
+            .filter(|x| *x < number_of_blocks as usize)
+            .collect();
+        let mut sorted = assignment.clone();
+        sorted.sort();
+        for x in assignment.iter_mut() {
+            *x = sorted.binary_search(x).unwrap();
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let bridge_hashmap = match helper::calc_arranged_bridge_hashmap(
+            number_of_blocks,
+            &geometry,
+            &mut rng,
+            &assignment,
+        ) {
+            Some(bridge_hashmap) => bridge_hashmap,
+            // not enough rows for the swaps
+            None => return Ok(()),
+        };
+
+        prop_assert_eq!(
+            helper::validate_bridges(&bridge_hashmap, number_of_blocks, &geometry),
+            Ok(())
+        );
+        prop_assert_eq!(
+            results_of(number_of_blocks, &bridge_hashmap, geometry.rows),
+            assignment
+        );
+    }
+
+    #[test]
+    fn validate_bridges_should_reject_rows_shared_with_neighbours(
+        number_of_blocks in 3..=12u8,
+        geometry in geometry(),
+        seed in any::<u64>(),
+        gap in any::<prop::sample::Index>(),
+    ) {
+        let geometry = Geometry { share_rows: false, ..geometry };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut bridge_hashmap = helper::calc_bridge_hashmap(number_of_blocks, &geometry, &mut rng);
+
+        // a row of the gap is copied to one of its neighbours
+        let gap = gap.index(number_of_blocks as usize - 1) as u16;
+        let neighbour = if gap == 0 { 1 } else { gap - 1 };
+        prop_assume!(!bridge_hashmap[&gap].is_empty());
+        let row = bridge_hashmap[&gap][0];
+
+        prop_assert!(helper::add_bridge(&mut bridge_hashmap, number_of_blocks, &geometry, neighbour, row).is_err());
+
+        let vec = bridge_hashmap.get_mut(&neighbour).unwrap();
+        vec.push(row);
+        vec.sort();
+        prop_assert!(helper::validate_bridges(&bridge_hashmap, number_of_blocks, &geometry).is_err());
+    }
+}
+
+#[test]
+fn validate_bridges_should_check_both_neighbours_of_the_first_gap() {
+    let geometry = Geometry {
+        min_bridges: 1,
+        ..Geometry::default()
+    };
+    let ladder = |gaps: &[&[u16]]| -> HashMap<u16, Vec<u16>> {
+        gaps.iter()
+            .enumerate()
+            .map(|(gap, rows)| (gap as u16, rows.to_vec()))
+            .collect()
+    };
+
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[1, 4], &[2], &[4]]), 4, &geometry),
+        Ok(())
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[2, 4], &[2], &[5]]), 4, &geometry),
+        Err(String::from("gaps 0 and 1 share row 2"))
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[4, 1], &[2], &[5]]), 4, &geometry),
+        Err(String::from("rows of gap 0 are not sorted: [4, 1]"))
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[1], &[2]]), 4, &geometry),
+        Err(String::from("gap 2 is missing"))
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[1], &[12]]), 3, &geometry),
+        Err(String::from("row 12 of gap 1 is out of rows 0..10"))
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[1, 4], &[], &[4]]), 4, &geometry),
+        Err(String::from(
+            "gap 1 has 0 bridges, fewer than min bridges 1"
+        ))
+    );
+    assert_eq!(
+        helper::validate_bridges(&ladder(&[&[1, 3, 5, 7, 8, 9], &[2]]), 3, &geometry),
+        Err(String::from("gap 0 has 6 bridges, more than max bridges 5"))
+    );
+    assert!(helper::validate_bridges(
+        &ladder(&[&[2], &[2]]),
+        3,
+        &Geometry {
+            share_rows: true,
+            ..geometry
+        }
+    )
+    .is_ok());
+}
@@ -1,6 +1,7 @@
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
 use rust_sadari_cli::helper;
 use std::{collections::HashSet, iter::FromIterator};
+use tui::layout::Rect;
 
 #[test]
 fn calc_name_layout_sum_is_100() {
@@ -22,7 +23,7 @@ fn calc_name_layout_sum_is_100() {
                     .enumerate()
                     .all(|(index, value)| value > &0 || index == 0 || index == v.len() - 1);
                 assert_eq!(sum, 100);
-                assert_eq!(*is_positive, true);
+                assert!(*is_positive);
             }
             Err((unit_width, string)) => {
                 println!("{}", &string);
@@ -41,7 +42,7 @@ fn calc_bridge_indexes_produce_rand_vec() {
 
     for _ in 0..1000 {
         let number_of_bridge = rng.gen_range(2, number_of_max_bridge);
-        let vec_candidate = (0..y_coordinate).into_iter().collect();
+        let vec_candidate = (0..y_coordinate).collect();
 
         let vec = helper::calc_bridge_indexes(&mut rng, number_of_bridge, vec_candidate);
         assert_eq!(vec.len(), number_of_bridge as usize);
@@ -55,9 +56,9 @@ fn calc_bridge_indexes_should_not_have_duplicate() {
     let number_of_max_bridge = 10;
 
     for number_of_bridge in 2..number_of_max_bridge {
-        let vec_candidate = (0..10).into_iter().collect();
+        let vec_candidate = (0..10).collect();
         let mut vec = helper::calc_bridge_indexes(&mut rng, number_of_bridge, vec_candidate);
-        &mut vec.sort();
+        vec.sort();
 
         let mut is_duplicate = false;
 
@@ -68,7 +69,7 @@ fn calc_bridge_indexes_should_not_have_duplicate() {
             }
         }
 
-        assert_eq!(is_duplicate, false);
+        assert!(!is_duplicate);
     }
 }
 
@@ -87,7 +88,7 @@ fn calc_distributed_height_should_well_distributed() {
             .all(|x| *x == possible_heights.0 || *x == possible_heights.1);
         assert!(is_well_distributed);
 
-        let sum = vec.iter().fold(0u16, |acc, x| acc + x);
+        let sum = vec.iter().sum::<u16>();
         assert_eq!(height, sum);
     }
 
@@ -105,15 +106,9 @@ fn calc_distributed_height_should_well_distributed() {
 fn calc_bridge_hashmap_should_distinct_indexes_vec_compared_to_adjacent_vec() {
     let mut rng = rand::thread_rng();
     let number_of_block = 10;
-    let nubmer_of_max_bridges = 6;
-    let y_coordinate = 10;
 
-    let bridge_hashmap = helper::calc_bridge_hashmap(
-        number_of_block,
-        nubmer_of_max_bridges,
-        y_coordinate,
-        &mut rng,
-    );
+    let bridge_hashmap =
+        helper::calc_bridge_hashmap(number_of_block, &helper::Geometry::default(), &mut rng);
 
     for (key, value) in &bridge_hashmap {
         println!("{}: {:?}", key, value);
@@ -132,7 +127,7 @@ fn calc_bridge_hashmap_should_distinct_indexes_vec_compared_to_adjacent_vec() {
                 assert!(is_duplicate, "There is duplicate!");
             }
             None => {
-                assert!(false, "There should be no None case!");
+                panic!("There should be no None case!");
             }
         }
     }
@@ -142,15 +137,9 @@ fn calc_bridge_hashmap_should_distinct_indexes_vec_compared_to_adjacent_vec() {
 fn calc_bridge_hashmap_is_sorted() {
     let mut rng = rand::thread_rng();
     let number_of_block = 10;
-    let nubmer_of_max_bridges = 6;
-    let y_coordinate = 10;
 
-    let bridge_hashmap = helper::calc_bridge_hashmap(
-        number_of_block,
-        nubmer_of_max_bridges,
-        y_coordinate,
-        &mut rng,
-    );
+    let bridge_hashmap =
+        helper::calc_bridge_hashmap(number_of_block, &helper::Geometry::default(), &mut rng);
 
     for (key, value) in &bridge_hashmap {
         println!("{}: {:?}", key, value);
@@ -190,15 +179,9 @@ fn calc_index_should_in_limit() {
 fn calc_bridge_points_should_not_overlap() {
     let mut rng = rand::thread_rng();
     let number_of_block = 10;
-    let nubmer_of_max_bridges = 6;
-    let y_coordinate = 10;
 
-    let bridge_hashmap = helper::calc_bridge_hashmap(
-        number_of_block,
-        nubmer_of_max_bridges,
-        y_coordinate,
-        &mut rng,
-    );
+    let bridge_hashmap =
+        helper::calc_bridge_hashmap(number_of_block, &helper::Geometry::default(), &mut rng);
 
     for index in 0..number_of_block as i32 {
         let vec = helper::calc_bridge_points(index, &bridge_hashmap);
@@ -233,27 +216,628 @@ fn calc_bridge_points_should_not_overlap() {
 pub fn calc_path_result_should_not_overlap() {
     let mut rng = rand::thread_rng();
     let number_of_block = 10;
-    let nubmer_of_max_bridges = 6;
     let y_coordinate = 10;
 
-    let bridge_hashmap = helper::calc_bridge_hashmap(
-        number_of_block,
-        nubmer_of_max_bridges,
-        y_coordinate,
-        &mut rng,
-    );
+    let bridge_hashmap =
+        helper::calc_bridge_hashmap(number_of_block, &helper::Geometry::default(), &mut rng);
 
     let mut result = HashSet::new();
 
     for index in 0..number_of_block {
         let path = helper::calc_path(index, &bridge_hashmap, y_coordinate as u8);
 
-        let helper::Point { x: last_x, y: _ } = path.get(path.len() - 1).unwrap();
+        let helper::Point { x: last_x, y: _ } = path.last().unwrap();
 
         let is_exist = result.contains(last_x);
-        assert_eq!(is_exist, false);
+        assert!(!is_exist);
         result.insert(*last_x);
     }
 
     println!("Test cal_path, result is {:?}", result);
 }
+
+#[test]
+fn calc_path_hashmap_should_have_path_for_every_block() {
+    let mut rng = rand::thread_rng();
+    let number_of_block = 10;
+    let y_coordinate = 10;
+
+    let bridge_hashmap =
+        helper::calc_bridge_hashmap(number_of_block, &helper::Geometry::default(), &mut rng);
+    let path_hashmap = helper::calc_path_hashmap(number_of_block, &bridge_hashmap, y_coordinate);
+
+    assert_eq!(path_hashmap.len(), number_of_block as usize);
+    for index in 0..number_of_block {
+        let path = path_hashmap.get(&index).unwrap();
+
+        assert_eq!(
+            path,
+            &helper::calc_path(index, &bridge_hashmap, y_coordinate as u8)
+        );
+    }
+}
+
+#[test]
+fn calc_sadari_layout_should_fail_below_min_terminal_size() {
+    let y_coordinate = 10;
+
+    for number_of_block in 2..13 {
+        let (width, height) = helper::calc_min_terminal_size(number_of_block, y_coordinate);
+
+        assert!(helper::calc_sadari_layout(
+            Rect::new(0, 0, width, height),
+            number_of_block,
+            y_coordinate,
+            0
+        )
+        .is_ok());
+        assert_eq!(
+            helper::calc_sadari_layout(
+                Rect::new(0, 0, width - 1, height),
+                number_of_block,
+                y_coordinate,
+                0
+            )
+            .unwrap_err(),
+            (width, height)
+        );
+        assert!(helper::calc_sadari_layout(
+            Rect::new(0, 0, width, height - 1),
+            number_of_block,
+            y_coordinate,
+            0
+        )
+        .is_err());
+    }
+}
+
+#[test]
+fn calc_sadari_layout_should_have_distinct_bridge_rows() {
+    let y_coordinate = 10;
+
+    for number_of_block in 2..13 {
+        let (width, height) = helper::calc_min_terminal_size(number_of_block, y_coordinate);
+
+        for area in [
+            Rect::new(0, 0, width, height),
+            Rect::new(0, 0, width + 37, height + 23),
+        ]
+        .iter()
+        {
+            let layout =
+                helper::calc_sadari_layout(*area, number_of_block, y_coordinate, 0).unwrap();
+
+            assert_eq!(layout.name_rects.len(), number_of_block as usize);
+            assert_eq!(layout.bridge_rects.len(), number_of_block as usize - 1);
+            assert!(layout.name_rects.iter().all(|r| r.width >= 3));
+
+            for i in 0..number_of_block as i32 {
+                let mut prev_y = layout
+                    .bridge_point_hashmap
+                    .get(&helper::Point::new(i, -1))
+                    .unwrap()
+                    .y;
+
+                for bridge_index in 0..=y_coordinate as i32 {
+                    let point = layout
+                        .bridge_point_hashmap
+                        .get(&helper::Point::new(i, bridge_index))
+                        .unwrap();
+
+                    assert!(point.y > prev_y, "bridge rows should not overlap");
+                    prev_y = point.y;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn calc_resized_tick_should_keep_progress() {
+    assert_eq!(helper::calc_resized_tick(0, 100, 50), 0);
+    assert_eq!(helper::calc_resized_tick(50, 100, 50), 25);
+    assert_eq!(helper::calc_resized_tick(100, 100, 200), 200);
+    // finished animation stays finished
+    assert_eq!(helper::calc_resized_tick(101, 100, 200), 201);
+    assert_eq!(helper::calc_resized_tick(500, 100, 50), 51);
+}
+
+#[test]
+fn calc_segment_ends_should_be_increasing_up_to_path_length() {
+    let (number_of_block, y_coordinate) = (5, 10);
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        number_of_block,
+        &helper::Geometry::default(),
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(number_of_block, &bridge_hashmap, y_coordinate);
+    let layout =
+        helper::calc_sadari_layout(Rect::new(0, 0, 150, 50), number_of_block, y_coordinate, 0)
+            .unwrap();
+
+    for (selected_chunk, path) in path_hashmap.iter() {
+        let path_length =
+            helper::calc_path_length(&layout.bridge_point_hashmap, path, *selected_chunk);
+
+        for reverse in [false, true].iter() {
+            let segment_ends = helper::calc_segment_ends(
+                &layout.bridge_point_hashmap,
+                path,
+                *selected_chunk,
+                *reverse,
+            );
+
+            assert_eq!(segment_ends.len(), path.len());
+            assert!(segment_ends.windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(*segment_ends.last().unwrap(), path_length);
+        }
+    }
+}
+
+#[test]
+fn calc_clicked_index_should_find_rect_under_point() {
+    let rects = vec![Rect::new(0, 0, 5, 3), Rect::new(10, 0, 5, 3)];
+
+    assert_eq!(helper::calc_clicked_index(&rects, 0, 0), Some(0));
+    assert_eq!(helper::calc_clicked_index(&rects, 14, 2), Some(1));
+    assert_eq!(helper::calc_clicked_index(&rects, 5, 1), None);
+    assert_eq!(helper::calc_clicked_index(&rects, 10, 3), None);
+}
+
+#[test]
+fn calc_name_index_of_result_should_reverse_path() {
+    let (number_of_block, y_coordinate) = (6, 10);
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        number_of_block,
+        &helper::Geometry::default(),
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(number_of_block, &bridge_hashmap, y_coordinate);
+
+    for name_index in 0..number_of_block {
+        let result_index = path_hashmap.get(&name_index).unwrap().last().unwrap().x;
+
+        assert_eq!(
+            helper::calc_name_index_of_result(&path_hashmap, result_index as u8),
+            Some(name_index)
+        );
+    }
+}
+
+#[test]
+fn calc_result_row_index_should_match_every_two_lines() {
+    let layout = helper::calc_result_layout(Rect::new(0, 0, 100, 40));
+    let column = layout.columns[0];
+
+    assert_eq!(
+        helper::calc_result_row_index(&layout, 3, column.x, column.y),
+        Some(0)
+    );
+    assert_eq!(
+        helper::calc_result_row_index(&layout, 3, column.x, column.y + 1),
+        None
+    );
+    assert_eq!(
+        helper::calc_result_row_index(&layout, 3, layout.columns[2].x, column.y + 4),
+        Some(2)
+    );
+    assert_eq!(
+        helper::calc_result_row_index(&layout, 3, column.x, column.y + 6),
+        None
+    );
+    assert_eq!(helper::calc_result_row_index(&layout, 3, 0, column.y), None);
+}
+
+#[test]
+fn calc_truncated_text_should_fit_display_width() {
+    assert_eq!(helper::calc_truncated_text("name", 4), "name");
+    assert_eq!(helper::calc_truncated_text("name1", 4), "nam…");
+    // wide characters take two columns
+    assert_eq!(helper::calc_text_width("홍길동"), 6);
+    assert_eq!(helper::calc_truncated_text("홍길동", 5), "홍길…");
+    assert_eq!(helper::calc_truncated_text("홍길동", 4), "홍…");
+    assert_eq!(helper::calc_truncated_text("🍺🍺", 3), "🍺…");
+    assert_eq!(helper::calc_truncated_text("name", 0), "");
+
+    assert_eq!(helper::calc_truncated_text_end("name1", 4), "…me1");
+    assert_eq!(helper::calc_truncated_text_end("홍길동", 5), "…길동");
+
+    for text in ["홍길동전", "name🍺name", "ｆｕｌｌｗｉｄｔｈ"].iter() {
+        for width in 0..12 {
+            assert!(helper::calc_text_width(&helper::calc_truncated_text(text, width)) <= width);
+            assert!(
+                helper::calc_text_width(&helper::calc_truncated_text_end(text, width)) <= width
+            );
+        }
+    }
+}
+
+#[test]
+fn calc_sadari_layout_should_widen_boxes_to_text_width() {
+    let (number_of_block, y_coordinate) = (3, 10);
+    let area = Rect::new(0, 0, 150, 50);
+    let layout = helper::calc_sadari_layout(area, number_of_block, y_coordinate, 0).unwrap();
+    let wide_layout = helper::calc_sadari_layout(area, number_of_block, y_coordinate, 40).unwrap();
+
+    // rails and bridges are not moved
+    assert_eq!(layout.rail_rects, wide_layout.rail_rects);
+    assert_eq!(
+        layout.bridge_point_hashmap,
+        wide_layout.bridge_point_hashmap
+    );
+
+    for (rect, wide_rect) in layout.name_rects.iter().zip(wide_layout.name_rects.iter()) {
+        assert!(wide_rect.width > rect.width);
+        assert_eq!(wide_rect.width, 42);
+    }
+
+    // boxes do not overlap when the text is too wide
+    let layout = helper::calc_sadari_layout(area, 12, y_coordinate, 100).unwrap();
+    for rects in [&layout.name_rects, &layout.result_rects].iter() {
+        assert!(rects.windows(2).all(|w| w[0].right() < w[1].x));
+        assert!(rects
+            .iter()
+            .all(|r| r.x >= area.x && r.right() <= area.right()));
+    }
+}
+
+#[test]
+fn calc_has_assignment_should_find_perfect_matching() {
+    let allowed = |rows: &[&[u8]]| -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.iter().map(|x| *x == 1).collect())
+            .collect()
+    };
+
+    assert!(helper::calc_has_assignment(&allowed(&[
+        &[0, 1, 1],
+        &[1, 0, 1],
+        &[1, 1, 0]
+    ])));
+    // the first two players can only get the first result
+    assert!(!helper::calc_has_assignment(&allowed(&[
+        &[1, 0, 0],
+        &[1, 0, 0],
+        &[1, 1, 1]
+    ])));
+    // found by moving the first player to another result
+    assert!(helper::calc_has_assignment(&allowed(&[
+        &[1, 1, 0],
+        &[1, 0, 0],
+        &[0, 1, 1]
+    ])));
+}
+
+#[test]
+fn calc_constrained_bridge_hashmap_should_satisfy_allowed_results() {
+    let mut rng = rand::thread_rng();
+
+    for number_of_block in 2..8u8 {
+        // derangement, nobody gets the result right below
+        let allowed: Vec<Vec<bool>> = (0..number_of_block)
+            .map(|i| (0..number_of_block).map(|j| i != j).collect())
+            .collect();
+
+        for _ in 0..20 {
+            let bridge_hashmap = helper::calc_constrained_bridge_hashmap(
+                number_of_block,
+                &helper::Geometry::default(),
+                &mut rng,
+                &allowed,
+            )
+            .unwrap();
+            let path_hashmap = helper::calc_path_hashmap(number_of_block, &bridge_hashmap, 10);
+
+            assert!(helper::calc_pairings(&path_hashmap)
+                .iter()
+                .all(|(name_index, result_index)| name_index != result_index));
+        }
+    }
+
+    let impossible = vec![vec![true, false], vec![true, false]];
+    assert!(helper::calc_constrained_bridge_hashmap(
+        2,
+        &helper::Geometry::default(),
+        &mut rng,
+        &impossible,
+    )
+    .is_err());
+}
+
+#[test]
+fn calc_arranged_bridge_hashmap_should_give_the_assignment() {
+    let mut rng = rand::thread_rng();
+    // pairs of bridges cancel each other in shared rows as well
+    let geometries = [
+        helper::Geometry::default(),
+        helper::Geometry {
+            share_rows: true,
+            ..helper::Geometry::default()
+        },
+    ];
+
+    for geometry in geometries.iter() {
+        for number_of_block in 2..=12u8 {
+            for _ in 0..50 {
+                let mut assignment: Vec<usize> = (0..number_of_block as usize).collect();
+                assignment.shuffle(&mut rng);
+
+                // rows left for the least bridges of a gap differ in each try
+                let bridge_hashmap = (0..100).find_map(|_| {
+                    helper::calc_arranged_bridge_hashmap(
+                        number_of_block,
+                        geometry,
+                        &mut rng,
+                        &assignment,
+                    )
+                });
+                let bridge_hashmap = match bridge_hashmap {
+                    Some(bridge_hashmap) => bridge_hashmap,
+                    // odd-even transposition needs up to a row per player
+                    None if number_of_block > 10 => continue,
+                    None => panic!("assignment {:?} is not arranged", assignment),
+                };
+                let path_hashmap =
+                    helper::calc_path_hashmap(number_of_block, &bridge_hashmap, geometry.rows);
+
+                for (name_index, result_index) in helper::calc_pairings(&path_hashmap) {
+                    assert_eq!(assignment[name_index as usize], result_index as usize);
+                }
+                // neighbouring gaps do not share rows
+                for gap in 1..(number_of_block - 1) as u16 {
+                    assert!(bridge_hashmap[&gap]
+                        .iter()
+                        .all(|row| !bridge_hashmap[&(gap - 1)].contains(row)));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn calc_constrained_bridge_hashmap_should_satisfy_pinned_results() {
+    let mut rng = rand::thread_rng();
+
+    // every name is pinned, reversed in each 4 names, random ladders almost
+    // never match it
+    let number_of_block = 12u8;
+    let allowed: Vec<Vec<bool>> = (0..number_of_block as usize)
+        .map(|i| {
+            (0..number_of_block as usize)
+                .map(|j| j == i / 4 * 4 + 3 - i % 4)
+                .collect()
+        })
+        .collect();
+
+    let bridge_hashmap = helper::calc_constrained_bridge_hashmap(
+        number_of_block,
+        &helper::Geometry::default(),
+        &mut rng,
+        &allowed,
+    )
+    .unwrap();
+    let path_hashmap = helper::calc_path_hashmap(number_of_block, &bridge_hashmap, 10);
+
+    for (name_index, result_index) in helper::calc_pairings(&path_hashmap) {
+        assert!(allowed[name_index as usize][result_index as usize]);
+    }
+}
+
+#[test]
+fn calc_bridge_hashmap_should_follow_geometry() {
+    let mut rng = rand::thread_rng();
+    let geometry = helper::Geometry {
+        rows: 14,
+        min_bridges: 3,
+        max_bridges: 7,
+        edge_rows: false,
+        ..helper::Geometry::default()
+    };
+
+    for _ in 0..100 {
+        let bridge_hashmap = helper::calc_bridge_hashmap(8, &geometry, &mut rng);
+
+        assert_eq!(bridge_hashmap.len(), 7);
+        for vec in bridge_hashmap.values() {
+            // 12 rows leave 5 rows at least next to 7 bridges
+            assert!((3..=7).contains(&vec.len()));
+            assert!(vec.iter().all(|row| *row >= 1 && *row < 13));
+        }
+    }
+}
+
+#[test]
+fn geometry_bridge_counts_should_meet_density() {
+    let mut rng = rand::thread_rng();
+    let geometry = helper::Geometry {
+        rows: 20,
+        min_bridges: 1,
+        max_bridges: 10,
+        density: Some(25),
+        ..helper::Geometry::default()
+    };
+
+    for _ in 0..100 {
+        let counts = geometry.bridge_counts(6, &mut rng);
+
+        assert_eq!(counts.iter().sum::<usize>(), 30);
+        assert!(counts.iter().all(|count| (1..=10).contains(count)));
+    }
+
+    // the total is kept within min and max bridges of every gap
+    let sparse = helper::Geometry {
+        density: Some(0),
+        ..geometry.clone()
+    };
+    assert_eq!(sparse.bridge_counts(6, &mut rng), vec![1; 6]);
+}
+
+#[test]
+fn geometry_check_should_reject_impossible_bridges() {
+    let geometry = helper::Geometry::default();
+    assert!(geometry.check().is_ok());
+
+    assert!(helper::Geometry {
+        min_bridges: 6,
+        ..geometry.clone()
+    }
+    .check()
+    .is_err());
+    assert!(helper::Geometry {
+        rows: 6,
+        edge_rows: false,
+        ..geometry.clone()
+    }
+    .check()
+    .is_err());
+    assert!(helper::Geometry {
+        rows: 0,
+        ..geometry.clone()
+    }
+    .check()
+    .is_err());
+
+    // neighbouring gaps do not fit in the rows unless they share rows
+    let few_rows = helper::Geometry {
+        rows: 6,
+        ..geometry
+    };
+    assert!(few_rows.check().is_err());
+    assert!(helper::Geometry {
+        max_bridges: 4,
+        ..few_rows.clone()
+    }
+    .check()
+    .is_ok());
+    assert!(helper::Geometry {
+        share_rows: true,
+        ..few_rows
+    }
+    .check()
+    .is_ok());
+}
+
+#[test]
+fn calc_bridge_hashmap_should_have_min_bridges_in_few_rows() {
+    let mut rng = rand::thread_rng();
+
+    // default bridge counts in the fewest rows they fit in, a gap used to
+    // get fewer rows than min bridges after its neighbour took the rest
+    for edge_rows in [true, false].iter() {
+        for density in [None, Some(100)].iter() {
+            let geometry = helper::Geometry {
+                rows: if *edge_rows { 7 } else { 9 },
+                density: *density,
+                edge_rows: *edge_rows,
+                ..helper::Geometry::default()
+            };
+            assert!(geometry.check().is_ok());
+
+            for _ in 0..200 {
+                let bridge_hashmap = helper::calc_bridge_hashmap(12, &geometry, &mut rng);
+
+                assert_eq!(
+                    helper::validate_bridges(&bridge_hashmap, 12, &geometry),
+                    Ok(())
+                );
+                assert!(bridge_hashmap
+                    .values()
+                    .all(|vec| (2..=5).contains(&vec.len())));
+            }
+        }
+    }
+}
+
+#[test]
+fn calc_path_should_cross_shared_rows_to_the_right_end() {
+    // gaps 0 and 1 share row 0, a single line over rails 0, 1 and 2
+    let bridge_hashmap: std::collections::HashMap<u16, Vec<u16>> =
+        vec![(0, vec![0]), (1, vec![0]), (2, vec![])]
+            .into_iter()
+            .collect();
+    let path_hashmap = helper::calc_path_hashmap(4, &bridge_hashmap, 2);
+
+    assert_eq!(
+        helper::calc_pairings(&path_hashmap),
+        vec![(0, 2), (1, 0), (2, 1), (3, 3)]
+    );
+    assert_eq!(
+        path_hashmap[&0],
+        vec![
+            helper::Point::new(0, 0),
+            helper::Point::new(1, 0),
+            helper::Point::new(2, 0),
+            helper::Point::new(2, 2)
+        ]
+    );
+}
+
+#[test]
+fn calc_path_should_be_permutation_with_shared_rows() {
+    let mut rng = rand::thread_rng();
+    let geometry = helper::Geometry {
+        share_rows: true,
+        min_bridges: 4,
+        max_bridges: 8,
+        ..helper::Geometry::default()
+    };
+
+    for number_of_block in 2..=12u8 {
+        for _ in 0..20 {
+            let bridge_hashmap = helper::calc_bridge_hashmap(number_of_block, &geometry, &mut rng);
+            let path_hashmap =
+                helper::calc_path_hashmap(number_of_block, &bridge_hashmap, geometry.rows);
+            let results: HashSet<u8> = helper::calc_pairings(&path_hashmap)
+                .into_iter()
+                .map(|(_, result_index)| result_index)
+                .collect();
+
+            assert_eq!(results.len(), number_of_block as usize);
+        }
+    }
+}
+
+#[test]
+fn calc_sadari_layout_should_fit_any_number_of_rows() {
+    for y_coordinate in [1, 3, 25, 60].iter().cloned() {
+        let (width, height) = helper::calc_min_terminal_size(6, y_coordinate);
+
+        for area in [
+            Rect::new(0, 0, width, height),
+            Rect::new(0, 0, width + 20, height + 40),
+        ]
+        .iter()
+        {
+            let layout = helper::calc_sadari_layout(*area, 6, y_coordinate, 0).unwrap();
+            let rail = layout.rail_rects[0];
+            let row_y = |row: i32| layout.bridge_point_hashmap[&helper::Point::new(0, row)].y;
+
+            assert!((-1..y_coordinate as i32).all(|row| row_y(row) < row_y(row + 1)));
+            assert!(
+                row_y(0) > rail.y as i32 && row_y(y_coordinate as i32 - 1) < rail.bottom() as i32
+            );
+        }
+    }
+}
+
+#[test]
+fn calc_min_terminal_size_should_be_the_smallest_height_for_any_rows() {
+    for y_coordinate in 1..=helper::MAX_ROWS {
+        let (width, height) = helper::calc_min_terminal_size(4, y_coordinate);
+        let layout = |height: u16| {
+            helper::calc_sadari_layout(Rect::new(0, 0, width, height), 4, y_coordinate, 0)
+        };
+
+        assert!(layout(height).is_ok());
+        assert!(layout(height - 1).is_err());
+    }
+
+    // tall terminals do not overflow the percentages of the layout
+    let area = Rect {
+        x: 0,
+        y: 0,
+        width: 60,
+        height: 5000,
+    };
+    assert!(helper::calc_sadari_layout(area, 4, 10, 0).is_ok());
+}
@@ -0,0 +1,74 @@
+use rust_sadari_cli::helper::{self, CopyFormat, Key, Keymap, ResultAction, ResultState};
+use std::collections::HashMap;
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn osc52_sequence_should_encode_text_in_base64() {
+    assert_eq!(helper::osc52_sequence("", false), "\x1b]52;c;\x07");
+    assert_eq!(helper::osc52_sequence("h", false), "\x1b]52;c;aA==\x07");
+    assert_eq!(helper::osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+    assert_eq!(
+        helper::osc52_sequence("hello", false),
+        "\x1b]52;c;aGVsbG8=\x07"
+    );
+    assert_eq!(
+        helper::osc52_sequence("사다리", false),
+        "\x1b]52;c;7IKs64uk66as\x07"
+    );
+    assert_eq!(
+        helper::osc52_sequence("hi", true),
+        "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+    );
+}
+
+#[test]
+fn format_mapping_should_write_plain_text_and_markdown() {
+    let name_vec = to_strings(&["Alice", "Bob|Carol"]);
+    let result_vec = to_strings(&["Free", "Pay"]);
+    let path_hashmap = helper::calc_path_hashmap(2, &HashMap::new(), 10);
+
+    assert_eq!(
+        helper::format_mapping(CopyFormat::Plain, &path_hashmap, &name_vec, &result_vec),
+        "Alice: Free\nBob|Carol: Pay"
+    );
+    assert_eq!(
+        helper::format_mapping(CopyFormat::Markdown, &path_hashmap, &name_vec, &result_vec),
+        "| Name | Result |\n| --- | --- |\n| Alice | Free |\n| Bob\\|Carol | Pay |"
+    );
+}
+
+#[test]
+fn result_state_should_ask_copy_but_not_while_searching() {
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+
+    assert_eq!(
+        result_state.handle_key(Key::Char('c'), &keymap),
+        ResultAction::Copy(CopyFormat::Plain)
+    );
+    assert_eq!(
+        result_state.handle_key(Key::Char('m'), &keymap),
+        ResultAction::Copy(CopyFormat::Markdown)
+    );
+
+    // copy keys follow the keymap
+    let custom = Keymap::parse("quit = c\ncopy = y").unwrap();
+    assert_eq!(
+        result_state.handle_key(Key::Char('c'), &custom),
+        ResultAction::Ignored
+    );
+    assert_eq!(
+        result_state.handle_key(Key::Char('y'), &custom),
+        ResultAction::Copy(CopyFormat::Plain)
+    );
+
+    result_state.handle_key(Key::Char('/'), &keymap);
+    assert_eq!(
+        result_state.handle_key(Key::Char('c'), &keymap),
+        ResultAction::Stay
+    );
+    assert_eq!(result_state.query, "c");
+}
@@ -0,0 +1,84 @@
+use rust_sadari_cli::helper::{self, Report, ReportFormat};
+use std::{collections::HashMap, path::PathBuf};
+
+const DATA_PATH: &str = "tests/data";
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn draft_rounds_should_be_a_full_order_and_reversed_in_snake() {
+    assert_eq!(helper::calc_positions(3), to_strings(&["1", "2", "3"]));
+    assert_eq!(helper::calc_picks(0, 4, false), vec![1]);
+    assert_eq!(helper::calc_picks(0, 4, true), vec![1, 8]);
+    assert_eq!(helper::calc_picks(3, 4, true), vec![4, 5]);
+
+    let name_vec = to_strings(&["Alice", "Bob", "Carol"]);
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    bridge_hashmap.insert(1, vec![5]);
+    let path_hashmap = helper::calc_path_hashmap(3, &bridge_hashmap, 10);
+
+    // Alice -> 3, Bob -> 1, Carol -> 2
+    let rounds = helper::calc_draft_rounds(&path_hashmap, &name_vec, true);
+    assert_eq!(
+        rounds,
+        vec![
+            to_strings(&["Bob", "Carol", "Alice"]),
+            to_strings(&["Alice", "Carol", "Bob"]),
+        ]
+    );
+    assert_eq!(
+        helper::format_draft(&rounds),
+        vec![
+            "Round 1: 1. Bob, 2. Carol, 3. Alice",
+            "Round 2: 4. Alice, 5. Carol, 6. Bob",
+        ]
+    );
+    assert_eq!(
+        helper::format_draft_json(&rounds),
+        r#"{"draft":[["Bob","Carol","Alice"],["Alice","Carol","Bob"]]}"#
+    );
+    assert_eq!(
+        helper::calc_draft_rounds(&path_hashmap, &name_vec, false).len(),
+        1
+    );
+
+    let result_vec = helper::calc_positions(3);
+    let report = Report {
+        date: String::from("2026-10-19 12:00:00 UTC"),
+        seed: None,
+        bridge_hashmap: &bridge_hashmap,
+        name_vec: &name_vec,
+        result_vec: &result_vec,
+        pairings: helper::calc_pairings(&path_hashmap),
+        teams: None,
+        rounds: &[],
+        draft: Some(rounds),
+    };
+    assert!(report
+        .format(ReportFormat::Plain)
+        .ends_with("\nDraft order\n  Round 1: 1. Bob, 2. Carol, 3. Alice\n  Round 2: 4. Alice, 5. Carol, 6. Bob\n"));
+    assert!(report
+        .format(ReportFormat::Csv)
+        .contains("\ndraft,\"Round 1: 1. Bob, 2. Carol, 3. Alice; Round 2: 4. Alice,"));
+}
+
+#[test]
+fn read_args_snake_should_fill_positions() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--snake"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert!(sadari_env.draft);
+    assert!(sadari_env.snake);
+    assert_eq!(sadari_env.result_vec, helper::calc_positions(5));
+}
@@ -0,0 +1,446 @@
+use rust_sadari_cli::helper::{
+    self, Animation, Keymap, RenderingState, ResultState, Round, SadariEnvironment, SetupState,
+    Theme,
+};
+use std::{collections::HashMap, path::PathBuf};
+use tui::{backend::TestBackend, style::Color, Terminal};
+
+const DATA_PATH: &str = "tests/data";
+
+fn read_sadari_env(path: &str) -> SadariEnvironment {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/{}", DATA_PATH, path));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+    ]
+    .into_iter();
+
+    helper::read_args(mock_args)
+}
+
+fn terminal_sizes() -> Vec<(u16, u16)> {
+    let mut vec = Vec::new();
+
+    for width in (0..120).step_by(7) {
+        for height in (0..60).step_by(3) {
+            vec.push((width, height));
+        }
+    }
+
+    vec
+}
+
+#[test]
+fn render_sadari_should_not_panic_in_any_terminal_size() {
+    let sadari_env = read_sadari_env("input_same_length.txt");
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+
+    for (width, height) in terminal_sizes() {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+
+        for tick in [0, 30, 1000].iter() {
+            let mut animation = Animation::new();
+            animation.tick = *tick;
+
+            let mut rendering_state = RenderingState::Drawing;
+            helper::render_sadari(
+                &mut terminal,
+                &sadari_env,
+                0,
+                &animation,
+                &mut rendering_state,
+                &bridge_hashmap,
+                &path_hashmap,
+                None,
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn render_result_and_setup_should_not_panic_in_any_terminal_size() {
+    let sadari_env = read_sadari_env("input_same_length.txt");
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+    let setup_state = SetupState::new();
+
+    let mut result_state = ResultState::new();
+    result_state.highlighted = Some(1);
+    result_state.grouped = true;
+
+    for (width, height) in terminal_sizes() {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+
+        helper::render_result(&mut terminal, &sadari_env, &path_hashmap, &mut result_state)
+            .unwrap();
+        helper::render_setup(&mut terminal, &setup_state, &sadari_env).unwrap();
+    }
+}
+
+#[test]
+fn no_color_theme_should_not_draw_any_color() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt");
+    sadari_env.theme = Theme::no_color();
+
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 50)).unwrap();
+    let mut animation = Animation::new();
+    animation.skip();
+
+    let mut rendering_state = RenderingState::Drawing;
+    helper::render_sadari(
+        &mut terminal,
+        &sadari_env,
+        0,
+        &animation,
+        &mut rendering_state,
+        &bridge_hashmap,
+        &path_hashmap,
+        None,
+    )
+    .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    assert!(buffer
+        .content()
+        .iter()
+        .all(|cell| cell.style.fg == Color::Reset && cell.style.bg == Color::Reset));
+    // path is still distinguished by modifiers
+    assert!(buffer
+        .content()
+        .iter()
+        .any(|cell| cell.style.modifier == Theme::no_color().path.modifier));
+}
+
+#[test]
+fn ascii_mode_should_draw_only_ascii_characters() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt");
+    sadari_env.ascii = true;
+
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+    let is_ascii = |terminal: &Terminal<TestBackend>| {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .all(|cell| cell.symbol.is_ascii())
+    };
+
+    // too small terminal as well
+    for (width, height) in [(120, 50), (30, 10)].iter() {
+        let mut terminal = Terminal::new(TestBackend::new(*width, *height)).unwrap();
+        let mut animation = Animation::new();
+        animation.skip();
+
+        let mut rendering_state = RenderingState::Drawing;
+        helper::render_sadari(
+            &mut terminal,
+            &sadari_env,
+            0,
+            &animation,
+            &mut rendering_state,
+            &bridge_hashmap,
+            &path_hashmap,
+            None,
+        )
+        .unwrap();
+        assert!(is_ascii(&terminal));
+
+        helper::render_result(
+            &mut terminal,
+            &sadari_env,
+            &path_hashmap,
+            &mut ResultState::new(),
+        )
+        .unwrap();
+        assert!(is_ascii(&terminal));
+
+        helper::render_setup(&mut terminal, &SetupState::new(), &sadari_env).unwrap();
+        assert!(is_ascii(&terminal));
+    }
+}
+
+#[test]
+fn wide_names_should_be_truncated_in_boxes_with_detail() {
+    let mut sadari_env = read_sadari_env("input_wide_names.txt");
+    sadari_env.ascii = false;
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+    let buffer_text = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    };
+
+    for (width, height) in terminal_sizes() {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let mut rendering_state = RenderingState::Idle;
+        helper::render_sadari(
+            &mut terminal,
+            &sadari_env,
+            2,
+            &Animation::new(),
+            &mut rendering_state,
+            &bridge_hashmap,
+            &path_hashmap,
+            None,
+        )
+        .unwrap();
+        helper::render_result(
+            &mut terminal,
+            &sadari_env,
+            &path_hashmap,
+            &mut ResultState::new(),
+        )
+        .unwrap();
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 40)).unwrap();
+    let mut rendering_state = RenderingState::Idle;
+    helper::render_sadari(
+        &mut terminal,
+        &sadari_env,
+        2,
+        &Animation::new(),
+        &mut rendering_state,
+        &bridge_hashmap,
+        &path_hashmap,
+        None,
+    )
+    .unwrap();
+
+    let text = buffer_text(&terminal);
+    assert!(text.contains('…'));
+    // full name of the selected player is shown below the ladder
+    assert!(text.contains("very long player name"));
+}
+
+#[test]
+fn team_mode_should_draw_a_box_for_every_team() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt")
+        .number_of_blocks(12)
+        .name_vec((1..=12).map(|i| format!("name{}", i)).collect());
+    sadari_env.teams = Some(5);
+    let sadari_env = sadari_env.fill_mode_results();
+    let bridge_hashmap = helper::calc_bridge_hashmap(
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+        &mut rand::thread_rng(),
+    );
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        &bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+
+    for (width, height) in terminal_sizes() {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        helper::render_result(
+            &mut terminal,
+            &sadari_env,
+            &path_hashmap,
+            &mut ResultState::new(),
+        )
+        .unwrap();
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 50)).unwrap();
+    helper::render_result(
+        &mut terminal,
+        &sadari_env,
+        &path_hashmap,
+        &mut ResultState::new(),
+    )
+    .unwrap();
+
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol.as_str())
+        .collect();
+    for team in 1..=5 {
+        assert!(text.contains(&format!("Team {} (", team)));
+    }
+    assert!(text.contains("name12"));
+}
+
+#[test]
+fn too_small_terminal_should_show_quit_keys_of_the_keymap() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt");
+    sadari_env.keymap = Keymap::parse("quit = esc, x").unwrap();
+    let path_hashmap = helper::calc_path_hashmap(5, &HashMap::new(), 10);
+
+    let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+    helper::render_sadari(
+        &mut terminal,
+        &sadari_env,
+        0,
+        &Animation::new(),
+        &mut RenderingState::Idle,
+        &HashMap::new(),
+        &path_hashmap,
+        None,
+    )
+    .unwrap();
+
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol.as_str())
+        .collect();
+    assert!(text.contains("esc/x : Quit"));
+    assert!(!text.contains("q : Quit"));
+}
+
+#[test]
+fn finished_tournament_should_draw_every_round_and_the_winner() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt");
+    sadari_env.tournament = true;
+    sadari_env.rounds = vec![
+        Round {
+            number: 1,
+            advanced: vec![String::from("name2"), String::from("name4")],
+            out: vec![
+                String::from("name1"),
+                String::from("name3"),
+                String::from("name5"),
+            ],
+        },
+        Round {
+            number: 2,
+            advanced: vec![String::from("name4")],
+            out: vec![String::from("name2")],
+        },
+    ];
+    let path_hashmap = helper::calc_path_hashmap(5, &HashMap::new(), 10);
+
+    for (width, height) in terminal_sizes() {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        helper::render_result(
+            &mut terminal,
+            &sadari_env,
+            &path_hashmap,
+            &mut ResultState::new(),
+        )
+        .unwrap();
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    helper::render_result(
+        &mut terminal,
+        &sadari_env,
+        &path_hashmap,
+        &mut ResultState::new(),
+    )
+    .unwrap();
+
+    let text: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol.as_str())
+        .collect();
+    assert!(text.contains("Round 1: name2, name4 advance"));
+    assert!(text.contains("Round 2: name4 advance, name2 out"));
+    assert!(text.contains("Winner: name4"));
+}
+
+#[test]
+fn render_sadari_should_draw_any_number_of_rows() {
+    let mut sadari_env = read_sadari_env("input_same_length.txt");
+
+    for rows in [2, 7, 40].iter() {
+        sadari_env.geometry = helper::Geometry {
+            rows: *rows,
+            min_bridges: 1,
+            max_bridges: 2,
+            ..helper::Geometry::default()
+        };
+        let bridge_hashmap = helper::calc_bridge_hashmap(
+            sadari_env.number_of_blocks,
+            &sadari_env.geometry,
+            &mut rand::thread_rng(),
+        );
+        let path_hashmap =
+            helper::calc_path_hashmap(sadari_env.number_of_blocks, &bridge_hashmap, *rows);
+        let (width, height) = helper::calc_min_terminal_size(sadari_env.number_of_blocks, *rows);
+
+        for (width, height) in [(width, height), (width + 30, height + 30)].iter() {
+            let mut terminal = Terminal::new(TestBackend::new(*width, *height)).unwrap();
+            let mut animation = Animation::new();
+            animation.skip();
+
+            let mut rendering_state = RenderingState::Drawing;
+            helper::render_sadari(
+                &mut terminal,
+                &sadari_env,
+                0,
+                &animation,
+                &mut rendering_state,
+                &bridge_hashmap,
+                &path_hashmap,
+                None,
+            )
+            .unwrap();
+
+            assert!(rendering_state == RenderingState::Done);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use rust_sadari_cli::helper;
+use std::path::PathBuf;
+
+const DATA_PATH: &str = "tests/data";
+
+fn read_args(args: &[&str]) -> helper::SadariEnvironment {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mut mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+    ];
+    mock_args.extend(args.iter().map(|arg| String::from(*arg)));
+
+    helper::read_args(mock_args.into_iter())
+}
+
+#[test]
+fn run_headless_should_print_teams() {
+    let sadari_env = read_args(&["--teams", "2", "--headless"]);
+    assert_eq!(sadari_env.result_vec, helper::calc_team_labels(5, 2));
+
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    helper::run_headless(&mut out, &mut err, sadari_env).unwrap();
+    assert!(err.is_empty());
+    let out = String::from_utf8(out).unwrap();
+
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("Team 1: "));
+    assert!(lines[1].starts_with("Team 2: "));
+    assert_eq!(out.matches("name").count(), 5);
+}
+
+#[test]
+fn run_headless_should_keep_notes_out_of_the_result() {
+    let mut sadari_env = read_args(&["--headless", "--json"]);
+    sadari_env.avoidance_note = Some(String::from("only 1 last draw could be avoided"));
+
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    helper::run_headless(&mut out, &mut err, sadari_env).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("{\"pairings\":"));
+    assert!(!out.contains("note"));
+    assert_eq!(
+        String::from_utf8(err).unwrap(),
+        "note: only 1 last draw could be avoided\n"
+    );
+}
+
+#[test]
+fn run_headless_should_need_names_by_file() {
+    let sadari_env = helper::read_args(vec![String::from("dummy path")].into_iter());
+
+    let error = helper::run_headless(Vec::new(), Vec::new(), sadari_env).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
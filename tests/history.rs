@@ -0,0 +1,131 @@
+use rust_sadari_cli::helper::{self, History};
+use std::{env, fs, path::PathBuf};
+
+const DATA_PATH: &str = "tests/data";
+
+fn read_args_with_history(history: &str, avoid_recent: &str) -> helper::SadariEnvironment {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let history_file = env::temp_dir().join(format!(
+        "sadari-history-{}-{}.txt",
+        std::process::id(),
+        avoid_recent
+    ));
+    fs::write(&history_file, history).unwrap();
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--avoid-recent"),
+        String::from(avoid_recent),
+        String::from("--history"),
+        String::from(history_file.to_str().unwrap()),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+    fs::remove_file(&history_file).unwrap();
+
+    sadari_env
+}
+
+#[test]
+fn history_should_parse_draws_and_find_recent_results() {
+    let history =
+        History::parse("# chores\nAlice = Dishes, Bob = Trash\n\nAlice = Trash, Bob = Dishes\n")
+            .unwrap();
+
+    assert_eq!(history.draws.len(), 2);
+    assert!(history.is_recent(1, "Alice", "Trash"));
+    assert!(!history.is_recent(1, "Alice", "Dishes"));
+    assert!(history.is_recent(2, "Alice", "Dishes"));
+    assert!(!history.is_recent(0, "Alice", "Trash"));
+
+    assert!(History::parse("Alice = Dishes, Bob")
+        .unwrap_err()
+        .contains("line 1"));
+}
+
+#[test]
+fn history_should_read_back_names_with_separators() {
+    let name_vec: Vec<String> = ["Kim, Jr.", "a=b", "#1", r"back\slash"]
+        .iter()
+        .map(|name| String::from(*name))
+        .collect();
+    let result_vec: Vec<String> = ["x, y", "=", "z", "w#"]
+        .iter()
+        .map(|result| String::from(*result))
+        .collect();
+
+    let draw = helper::format_draw(
+        &helper::calc_path_hashmap(4, &std::collections::HashMap::new(), 10),
+        &name_vec,
+        &result_vec,
+    );
+    assert_eq!(
+        draw,
+        r"Kim\, Jr. = x\, y, a\=b = \=, \#1 = z, back\\slash = w\#"
+    );
+
+    let history = History::parse(&draw).unwrap();
+    assert_eq!(
+        history.draws,
+        vec![name_vec.into_iter().zip(result_vec).collect::<Vec<_>>()]
+    );
+}
+
+#[test]
+fn recent_results_should_be_avoided() {
+    let sadari_env = read_args_with_history(
+        "name1 = result1, name2 = result2, name3 = result3, name4 = result4, name5 = result5
+name1 = result2, name2 = result3, name3 = result4, name4 = result5, name5 = result1
+",
+        "2",
+    );
+    assert_eq!(sadari_env.avoided_draws, 2);
+    assert_eq!(sadari_env.avoidance_note, None);
+
+    let mut rng = sadari_env.rng();
+    for _ in 0..20 {
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let path_hashmap = helper::calc_path_hashmap(5, &bridge_hashmap, 10);
+
+        for (name_index, result_index) in helper::calc_pairings(&path_hashmap) {
+            assert_ne!(name_index, result_index);
+            assert_ne!((name_index + 1) % 5, result_index);
+        }
+    }
+
+    let draw = helper::format_draw(
+        &helper::calc_path_hashmap(5, &std::collections::HashMap::new(), 10),
+        &sadari_env.name_vec,
+        &sadari_env.result_vec,
+    );
+    assert_eq!(
+        draw,
+        "name1 = result1, name2 = result2, name3 = result3, name4 = result4, name5 = result5"
+    );
+}
+
+#[test]
+fn avoidance_should_be_relaxed_with_note() {
+    // name1 had every result but result5 in the last 4 draws, and every
+    // result in the last 5 draws
+    let sadari_env = read_args_with_history(
+        "name1 = result5
+name1 = result4
+name1 = result3
+name1 = result2
+name1 = result1
+",
+        "5",
+    );
+
+    assert_eq!(sadari_env.avoided_draws, 4);
+    assert_eq!(
+        sadari_env.avoidance_note,
+        Some(String::from(
+            "Results of only the last 4 of 5 draws could be avoided"
+        ))
+    );
+}
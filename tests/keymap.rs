@@ -0,0 +1,79 @@
+use rust_sadari_cli::helper::{self, Action, Key, Keymap};
+
+#[test]
+fn keymap_default_should_bind_every_guide_key() {
+    let keymap = Keymap::default();
+
+    assert_eq!(keymap.action(Key::Char('q')), Some(Action::Quit));
+    assert_eq!(keymap.action(Key::Char('\n')), Some(Action::Start));
+    assert_eq!(keymap.action(Key::Left), Some(Action::Prev));
+    assert_eq!(keymap.action(Key::Char('x')), None);
+    assert_eq!(keymap.exit_keys(), vec![Key::Char('q'), Key::Ctrl('c')]);
+}
+
+#[test]
+fn keymap_parse_should_take_keys_from_other_actions() {
+    let keymap = Keymap::parse("# comment\n\nstep = n\nnew_ladder = ctrl+n, F5\n").unwrap();
+
+    assert_eq!(keymap.action(Key::Char('n')), Some(Action::Step));
+    assert_eq!(keymap.action(Key::Char('.')), None);
+    assert_eq!(keymap.keys(Action::NewLadder), &[Key::Ctrl('n'), Key::F(5)]);
+    // not mentioned actions keep default keys
+    assert_eq!(keymap.keys(Action::Quit), &[Key::Char('q'), Key::Ctrl('c')]);
+}
+
+#[test]
+fn keymap_parse_should_report_invalid_lines() {
+    assert!(Keymap::parse("jump = j")
+        .unwrap_err()
+        .contains("unknown action jump"));
+    assert!(Keymap::parse("quit")
+        .unwrap_err()
+        .contains("line 1: expected"));
+    assert!(Keymap::parse("\nstart = ctrl+enter")
+        .unwrap_err()
+        .contains("line 2: unknown key ctrl+enter"));
+    assert!(Keymap::parse("toggle_result = q, ctrl+c")
+        .unwrap_err()
+        .contains("quit should have a key"));
+}
+
+#[test]
+fn parse_key_should_read_key_names() {
+    let test_set = [
+        ("enter", Key::Char('\n')),
+        ("Space", Key::Char(' ')),
+        ("comma", Key::Char(',')),
+        ("E", Key::Char('E')),
+        ("ctrl+x", Key::Ctrl('x')),
+        ("alt+X", Key::Alt('X')),
+        ("f12", Key::F(12)),
+        ("pageup", Key::PageUp),
+    ];
+
+    test_set.iter().for_each(|(text, key)| {
+        assert_eq!(helper::parse_key(text).unwrap(), *key);
+    });
+    assert!(helper::parse_key("f13").is_err());
+
+    // names in the guide text can be read back, except arrows
+    test_set.iter().for_each(|(_, key)| {
+        let name = helper::key_name(*key);
+        assert_eq!(helper::parse_key(&name).unwrap(), *key, "{}", name);
+    });
+}
+
+#[test]
+fn keymap_guide_text_should_follow_bindings() {
+    let keymap = Keymap::parse("quit = esc\nprev = a\nnext = d").unwrap();
+    let guide_text = keymap.guide_text();
+    let lines: Vec<&str> = guide_text.lines().collect();
+
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("a, d"));
+    assert!(guide_text.contains("esc"));
+    assert!(!guide_text.contains("←"));
+    assert!(lines
+        .iter()
+        .all(|line| line.chars().count() == lines[0].chars().count()));
+}
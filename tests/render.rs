@@ -0,0 +1,368 @@
+use rust_sadari_cli::helper::{
+    self, Animation, Point, RenderingState, ResultState, SadariEnvironment, Theme,
+};
+use std::{collections::HashMap, env, fs, path::PathBuf};
+use tui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    Terminal,
+};
+
+const SNAPSHOT_PATH: &str = "tests/data/snapshots";
+const NAMES: [&str; 12] = [
+    "Alice", "Bob", "Carol", "Dan", "Eve", "Frank", "Grace", "Heidi", "Ivan", "Judy", "Mallory",
+    "Niaj",
+];
+
+/// Path and the reached result box have colours of their own
+fn test_theme() -> Theme {
+    Theme {
+        path: Style::default().fg(Color::LightMagenta),
+        result: Style::default().fg(Color::LightCyan),
+        ..Theme::dark()
+    }
+}
+
+fn sadari_env(number_of_blocks: u8) -> SadariEnvironment {
+    let names: Vec<String> = NAMES[..number_of_blocks as usize]
+        .iter()
+        .map(|name| String::from(*name))
+        .collect();
+    let results = (1..=number_of_blocks).map(|x| format!("#{}", x)).collect();
+
+    let mut sadari_env = helper::read_args(vec![String::from("dummy path")].into_iter())
+        .number_of_blocks(number_of_blocks)
+        .name_vec(names)
+        .result_vec(results);
+    sadari_env.ascii = true;
+    sadari_env.theme = test_theme();
+
+    sadari_env
+}
+
+/// Ladder which does not depend on the random generator, even gaps have
+/// bridges in rows 1 and 5, odd gaps in rows 3 and 7
+fn fixed_bridge_hashmap(sadari_env: &SadariEnvironment) -> HashMap<u16, Vec<u16>> {
+    let bridge_hashmap = (0..sadari_env.number_of_blocks as u16 - 1)
+        .map(|gap| (gap, if gap % 2 == 0 { vec![1, 5] } else { vec![3, 7] }))
+        .collect();
+    helper::validate_bridges(
+        &bridge_hashmap,
+        sadari_env.number_of_blocks,
+        &sadari_env.geometry,
+    )
+    .unwrap();
+
+    bridge_hashmap
+}
+
+fn render(
+    sadari_env: &SadariEnvironment,
+    bridge_hashmap: &HashMap<u16, Vec<u16>>,
+    selected_chunk: u8,
+    tick: i32,
+    size: (u16, u16),
+) -> (Buffer, bool) {
+    let mut animation = Animation::new();
+    animation.tick = tick;
+
+    render_animation(sadari_env, bridge_hashmap, selected_chunk, &animation, size)
+}
+
+fn render_animation(
+    sadari_env: &SadariEnvironment,
+    bridge_hashmap: &HashMap<u16, Vec<u16>>,
+    selected_chunk: u8,
+    animation: &Animation,
+    (width, height): (u16, u16),
+) -> (Buffer, bool) {
+    let path_hashmap = helper::calc_path_hashmap(
+        sadari_env.number_of_blocks,
+        bridge_hashmap,
+        sadari_env.geometry.rows,
+    );
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+
+    let mut rendering_state = RenderingState::Drawing;
+    helper::render_sadari(
+        &mut terminal,
+        sadari_env,
+        selected_chunk,
+        animation,
+        &mut rendering_state,
+        bridge_hashmap,
+        &path_hashmap,
+        None,
+    )
+    .unwrap();
+
+    (
+        terminal.backend().buffer().clone(),
+        rendering_state == RenderingState::Done,
+    )
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area();
+
+    (area.top()..area.bottom())
+        .map(|y| {
+            let line: String = (area.left()..area.right())
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Compares the buffer with the snapshot file, which is written only when
+/// `SADARI_UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push(format!("{}/{}.txt", SNAPSHOT_PATH, name));
+    let text = buffer_text(buffer);
+
+    if env::var_os("SADARI_UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &text).unwrap();
+        return;
+    }
+
+    let snapshot = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "snapshot {} is not readable ({}), set SADARI_UPDATE_SNAPSHOTS=1 to write it",
+            name, err
+        )
+    });
+    assert!(
+        snapshot == text,
+        "snapshot {} differs, set SADARI_UPDATE_SNAPSHOTS=1 to update it\n\
+         --- snapshot\n{}\n--- rendered\n{}",
+        name,
+        snapshot,
+        text
+    );
+}
+
+/// Cells drawn in `color`
+fn cells_of(buffer: &Buffer, color: Color) -> Vec<(u16, u16)> {
+    let area = buffer.area();
+
+    (area.top()..area.bottom())
+        .flat_map(|y| (area.left()..area.right()).map(move |x| (x, y)))
+        .filter(|(x, y)| buffer.get(*x, *y).style.fg == color)
+        .collect()
+}
+
+fn contains(rect: &Rect, (x, y): (u16, u16)) -> bool {
+    x >= rect.x && x < rect.right() && y >= rect.y && y < rect.bottom()
+}
+
+#[test]
+fn render_sadari_should_match_snapshots() {
+    for (number_of_blocks, size) in [(3, (80, 40)), (6, (120, 50))].iter() {
+        let sadari_env = sadari_env(*number_of_blocks);
+        let bridge_hashmap = fixed_bridge_hashmap(&sadari_env);
+
+        let (buffer, _) = render(&sadari_env, &bridge_hashmap, 0, 20, *size);
+        assert_snapshot(&format!("sadari_{}_drawing", number_of_blocks), &buffer);
+
+        let (buffer, _) = render(&sadari_env, &bridge_hashmap, 1, i32::MAX, *size);
+        assert_snapshot(&format!("sadari_{}_done", number_of_blocks), &buffer);
+    }
+}
+
+#[test]
+fn render_result_should_match_snapshot() {
+    let sadari_env = sadari_env(4);
+    let bridge_hashmap = fixed_bridge_hashmap(&sadari_env);
+    let path_hashmap = helper::calc_path_hashmap(4, &bridge_hashmap, sadari_env.geometry.rows);
+    let mut terminal = Terminal::new(TestBackend::new(90, 36)).unwrap();
+
+    helper::render_result(
+        &mut terminal,
+        &sadari_env,
+        &path_hashmap,
+        &mut ResultState::new(),
+    )
+    .unwrap();
+
+    assert_snapshot("result_4", terminal.backend().buffer());
+}
+
+#[test]
+fn animated_path_should_end_in_the_result_box_of_calc_path() {
+    let mut rng = rand::thread_rng();
+
+    for number_of_blocks in 2..=12u8 {
+        let sadari_env = sadari_env(number_of_blocks);
+        let rows = sadari_env.geometry.rows;
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let (min_width, min_height) = helper::calc_min_terminal_size(number_of_blocks, rows);
+
+        for size in [
+            (min_width, min_height),
+            (min_width + 13, min_height + 7),
+            (200, 80),
+        ]
+        .iter()
+        {
+            let area = Rect::new(0, 0, size.0, size.1);
+            let layout =
+                helper::calc_sadari_layout(area, number_of_blocks, rows, sadari_env.text_width())
+                    .unwrap();
+
+            for selected_chunk in 0..number_of_blocks {
+                let path = helper::calc_path(selected_chunk, &bridge_hashmap, rows as u8);
+                let Point { x: end, y: _ } = *path.last().unwrap();
+                let (start_rail, end_rail) = (
+                    layout.rail_rects[selected_chunk as usize],
+                    layout.rail_rects[end as usize],
+                );
+                let result_rect = layout.result_rects[end as usize];
+
+                let (buffer, is_done) = render(
+                    &sadari_env,
+                    &bridge_hashmap,
+                    selected_chunk,
+                    i32::MAX,
+                    *size,
+                );
+                assert!(is_done);
+
+                // only the box of the result is highlighted, on its border
+                let result_cells = cells_of(&buffer, Color::LightCyan);
+                assert!(!result_cells.is_empty());
+                assert!(result_cells
+                    .iter()
+                    .all(|cell| contains(&result_rect, *cell)));
+                assert!(result_cells.contains(&(result_rect.x, result_rect.y)));
+
+                // the path goes from the rail of the name down to the rail of
+                // the result, right above the box
+                let path_cells = cells_of(&buffer, Color::LightMagenta);
+                let top = path_cells.iter().min_by_key(|(_, y)| *y).unwrap();
+                let bottom = path_cells.iter().max_by_key(|(_, y)| *y).unwrap();
+                assert_eq!(*top, (start_rail.x, start_rail.y));
+                assert_eq!(*bottom, (end_rail.x, result_rect.y - 1));
+            }
+        }
+    }
+}
+
+#[test]
+fn unfinished_animation_should_not_highlight_a_result() {
+    let sadari_env = sadari_env(5);
+    let bridge_hashmap = fixed_bridge_hashmap(&sadari_env);
+
+    for tick in [0, 5, 30].iter() {
+        let (buffer, is_done) = render(&sadari_env, &bridge_hashmap, 2, *tick, (100, 40));
+
+        assert!(!is_done);
+        assert!(cells_of(&buffer, Color::LightCyan).is_empty());
+        assert_eq!(cells_of(&buffer, Color::LightMagenta).len(), *tick as usize);
+    }
+}
+
+#[test]
+fn traced_back_path_should_start_from_the_result_box() {
+    let sadari_env = sadari_env(5);
+    let bridge_hashmap = fixed_bridge_hashmap(&sadari_env);
+    let rows = sadari_env.geometry.rows;
+    let layout = helper::calc_sadari_layout(Rect::new(0, 0, 100, 40), 5, rows, 0).unwrap();
+
+    for selected_chunk in 0..5u8 {
+        let end = helper::calc_path(selected_chunk, &bridge_hashmap, rows as u8)
+            .last()
+            .unwrap()
+            .x;
+        let (result_rect, end_rail) = (
+            layout.result_rects[end as usize],
+            layout.rail_rects[end as usize],
+        );
+        let mut animation = Animation::new();
+        animation.trace_back();
+        animation.tick = 1;
+
+        // the first cell is right above the box of the result
+        let (buffer, is_done) = render_animation(
+            &sadari_env,
+            &bridge_hashmap,
+            selected_chunk,
+            &animation,
+            (100, 40),
+        );
+        assert!(!is_done);
+        assert_eq!(
+            cells_of(&buffer, Color::LightMagenta),
+            vec![(end_rail.x, result_rect.y - 1)]
+        );
+
+        // the whole path is the same in both directions
+        animation.tick = i32::MAX;
+        let (buffer, is_done) = render_animation(
+            &sadari_env,
+            &bridge_hashmap,
+            selected_chunk,
+            &animation,
+            (100, 40),
+        );
+        let (forward, _) = render(
+            &sadari_env,
+            &bridge_hashmap,
+            selected_chunk,
+            i32::MAX,
+            (100, 40),
+        );
+        assert!(is_done);
+        assert_eq!(
+            cells_of(&buffer, Color::LightMagenta),
+            cells_of(&forward, Color::LightMagenta)
+        );
+    }
+}
+
+#[test]
+fn result_page_should_show_every_pairing_of_calc_path() {
+    let mut rng = rand::thread_rng();
+
+    for number_of_blocks in 2..=12u8 {
+        let sadari_env = sadari_env(number_of_blocks);
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let path_hashmap =
+            helper::calc_path_hashmap(number_of_blocks, &bridge_hashmap, sadari_env.geometry.rows);
+
+        for size in [(80, 45), (140, 60)].iter() {
+            let mut terminal = Terminal::new(TestBackend::new(size.0, size.1)).unwrap();
+            helper::render_result(
+                &mut terminal,
+                &sadari_env,
+                &path_hashmap,
+                &mut ResultState::new(),
+            )
+            .unwrap();
+            let text = buffer_text(terminal.backend().buffer());
+
+            for name_index in 0..number_of_blocks {
+                let result_index = path_hashmap[&name_index].last().unwrap().x as usize;
+                let (name, result) = (
+                    &sadari_env.name_vec[name_index as usize],
+                    &sadari_env.result_vec[result_index],
+                );
+
+                assert!(
+                    text.lines().any(|line| {
+                        let words: Vec<&str> = line.split_whitespace().collect();
+                        words.contains(&name.as_str()) && words.contains(&result.as_str())
+                    }),
+                    "{} -> {} is not shown\n{}",
+                    name,
+                    result,
+                    text
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use rust_sadari_cli::helper::{self, Report, ReportFormat};
+use std::collections::HashMap;
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn format_utc_date_should_convert_unix_time() {
+    assert_eq!(helper::format_utc_date(0), "1970-01-01 00:00:00 UTC");
+    assert_eq!(
+        helper::format_utc_date(951_868_799),
+        "2000-02-29 23:59:59 UTC"
+    );
+    assert_eq!(
+        helper::format_utc_date(1_792_411_200),
+        "2026-10-19 12:00:00 UTC"
+    );
+}
+
+#[test]
+fn markdown_cell_should_escape_pipes_and_backslashes() {
+    assert_eq!(helper::markdown_cell("Alice"), "Alice");
+    assert_eq!(helper::markdown_cell("Bob|Carol"), r"Bob\|Carol");
+    assert_eq!(helper::markdown_cell(r"C:\temp"), r"C:\\temp");
+    // an escaped pipe in the text stays inside the cell
+    assert_eq!(helper::markdown_cell(r"a\|b"), r"a\\\|b");
+}
+
+#[test]
+fn report_should_have_every_field_in_each_format() {
+    let name_vec = to_strings(&["Alice", "Bob, Jr.", "Carol"]);
+    let result_vec = to_strings(&["Free", "Pay", "Wash"]);
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    bridge_hashmap.insert(1, vec![5]);
+    let path_hashmap = helper::calc_path_hashmap(3, &bridge_hashmap, 10);
+
+    let report = Report {
+        date: String::from("2026-10-19 12:00:00 UTC"),
+        seed: Some(42),
+        bridge_hashmap: &bridge_hashmap,
+        name_vec: &name_vec,
+        result_vec: &result_vec,
+        pairings: helper::calc_pairings(&path_hashmap),
+        teams: None,
+        rounds: &[],
+        draft: None,
+    };
+    assert_eq!(report.pairings, vec![(0, 2), (1, 0), (2, 1)]);
+
+    assert_eq!(
+        report.format(ReportFormat::Markdown),
+        "# Rust-Sadari-Cli result
+
+- Date: 2026-10-19 12:00:00 UTC
+- Seed: 42
+- Participants: Alice, Bob, Jr., Carol
+
+## Bridges
+
+- 1-2: 3
+- 2-3: 6
+
+## Mapping
+
+| Name | Result |
+| --- | --- |
+| Alice | Wash |
+| Bob, Jr. | Free |
+| Carol | Pay |
+"
+    );
+
+    let plain = report.format(ReportFormat::Plain);
+    assert!(plain.starts_with("Rust-Sadari-Cli result\n"));
+    assert!(plain.contains("Seed: 42\n"));
+    assert!(plain.contains("  1-2: 3\n"));
+    assert!(plain.ends_with("  Bob, Jr. -> Free\n  Carol -> Pay\n"));
+
+    assert_eq!(
+        report.format(ReportFormat::Csv),
+        "title,Rust-Sadari-Cli result
+date,2026-10-19 12:00:00 UTC
+seed,42
+participants,\"Alice; Bob, Jr.; Carol\"
+bridges,1-2: 3; 2-3: 6
+
+name,result
+Alice,Wash
+\"Bob, Jr.\",Free
+Carol,Pay
+"
+    );
+}
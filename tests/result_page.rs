@@ -0,0 +1,217 @@
+use rust_sadari_cli::helper::{
+    self, Key, Keymap, ResultAction, ResultOrder, ResultState, SadariEnvironment,
+};
+use std::collections::HashMap;
+use tui::layout::Rect;
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+/// Ladder without bridges, every name gets the result below it
+fn straight_ladder(
+    names: &[&str],
+    results: &[&str],
+) -> (SadariEnvironment, HashMap<u8, Vec<helper::Point>>) {
+    let sadari_env = helper::read_args(vec![String::from("dummy path")].into_iter())
+        .number_of_blocks(names.len() as u8)
+        .name_vec(to_strings(names))
+        .result_vec(to_strings(results));
+    let path_hashmap = helper::calc_path_hashmap(names.len() as u8, &HashMap::new(), 10);
+
+    (sadari_env, path_hashmap)
+}
+
+fn row_names(
+    result_state: &ResultState,
+    sadari_env: &SadariEnvironment,
+    path_hashmap: &HashMap<u8, Vec<helper::Point>>,
+) -> Vec<String> {
+    result_state
+        .rows(sadari_env, path_hashmap)
+        .iter()
+        .map(|row| sadari_env.name_vec[row.name_index as usize].clone())
+        .collect()
+}
+
+#[test]
+fn result_state_should_sort_by_name_and_result() {
+    let (sadari_env, path_hashmap) =
+        straight_ladder(&["carol", "Alice", "bob"], &["Pay", "Wash", "Free"]);
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["carol", "Alice", "bob"]
+    );
+
+    result_state.handle_key(Key::Char('o'), &keymap);
+    assert_eq!(result_state.order, ResultOrder::Name);
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["Alice", "bob", "carol"]
+    );
+
+    result_state.handle_key(Key::Char('o'), &keymap);
+    assert_eq!(result_state.order, ResultOrder::Result);
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["bob", "carol", "Alice"]
+    );
+
+    result_state.handle_key(Key::Char('o'), &keymap);
+    assert_eq!(result_state.order, ResultOrder::Input);
+}
+
+#[test]
+fn result_state_should_group_identical_results() {
+    let (sadari_env, path_hashmap) = straight_ladder(
+        &["a", "b", "c", "d", "e"],
+        &["Free", "Pay", "Free", "Pay", "Free"],
+    );
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+    result_state.handle_key(Key::Char('g'), &keymap);
+
+    let rows = result_state.rows(&sadari_env, &path_hashmap);
+    let names: Vec<u8> = rows.iter().map(|row| row.name_index).collect();
+    let group_sizes: Vec<Option<usize>> = rows.iter().map(|row| row.group_size).collect();
+
+    assert_eq!(names, vec![0, 2, 4, 1, 3]);
+    assert_eq!(group_sizes, vec![Some(3), None, None, Some(2), None]);
+}
+
+#[test]
+fn result_state_should_group_results_by_sort_key() {
+    let (sadari_env, path_hashmap) = straight_ladder(&["a", "b", "c"], &["Free", "free", "Free"]);
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+    result_state.handle_key(Key::Char('g'), &keymap);
+
+    // results sorted together are one group regardless of the case
+    let rows = result_state.rows(&sadari_env, &path_hashmap);
+    let group_sizes: Vec<Option<usize>> = rows.iter().map(|row| row.group_size).collect();
+
+    assert_eq!(group_sizes, vec![Some(3), None, None]);
+}
+
+#[test]
+fn result_state_should_search_names_until_cancelled() {
+    let (sadari_env, path_hashmap) = straight_ladder(&["Alice", "Bob", "Alicia"], &["1", "2", "3"]);
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+
+    assert_eq!(
+        result_state.handle_key(Key::Char('/'), &keymap),
+        ResultAction::Stay
+    );
+    assert!(result_state.searching);
+    // keys of the result page are typed into the query while searching
+    "ali".chars().for_each(|c| {
+        assert_eq!(
+            result_state.handle_key(Key::Char(c), &keymap),
+            ResultAction::Stay
+        );
+    });
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["Alice", "Alicia"]
+    );
+
+    result_state.handle_key(Key::Char('\n'), &keymap);
+    assert!(!result_state.searching);
+    assert_eq!(result_state.query, "ali");
+    assert_eq!(
+        result_state.handle_key(Key::Char('q'), &keymap),
+        ResultAction::Ignored
+    );
+
+    result_state.handle_key(Key::Esc, &keymap);
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap).len(),
+        3
+    );
+    assert_eq!(
+        result_state.handle_key(Key::Esc, &keymap),
+        ResultAction::Ignored
+    );
+}
+
+#[test]
+fn result_state_should_take_keys_of_the_keymap() {
+    let (sadari_env, path_hashmap) = straight_ladder(&["b", "a"], &["1", "1"]);
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::parse("quit = g, /\ngroup = G\nsearch = ctrl+f\norder = esc").unwrap();
+
+    // keys bound to quit are left to the app
+    assert_eq!(
+        result_state.handle_key(Key::Char('g'), &keymap),
+        ResultAction::Ignored
+    );
+    assert_eq!(
+        result_state.handle_key(Key::Char('/'), &keymap),
+        ResultAction::Ignored
+    );
+    assert!(!result_state.grouped && !result_state.searching);
+
+    result_state.handle_key(Key::Char('G'), &keymap);
+    assert!(result_state.grouped);
+    result_state.handle_key(Key::Esc, &keymap);
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["a", "b"]
+    );
+
+    assert_eq!(
+        result_state.handle_key(Key::Ctrl('f'), &keymap),
+        ResultAction::Stay
+    );
+    assert!(result_state.searching);
+}
+
+#[test]
+fn result_state_should_scroll_within_rows() {
+    let (sadari_env, path_hashmap) = straight_ladder(
+        &["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"],
+        &[
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ],
+    );
+    let mut result_state = ResultState::new();
+    let keymap = Keymap::default();
+    result_state.clamp_scroll(12, 5);
+
+    result_state.handle_key(Key::PageDown, &keymap);
+    assert_eq!(result_state.scroll, 5);
+    result_state.handle_key(Key::End, &keymap);
+    result_state.clamp_scroll(12, 5);
+    assert_eq!(result_state.scroll, 7);
+    result_state.handle_key(Key::Up, &keymap);
+    assert_eq!(result_state.scroll, 6);
+
+    // clicked row is found in the scrolled rows
+    let layout = helper::calc_result_layout(Rect::new(0, 0, 100, 30));
+    let rows = result_state.rows(&sadari_env, &path_hashmap);
+    let column = layout.columns[0];
+    assert_eq!(
+        result_state.row_at(&layout, &rows, column.x, column.y + 2),
+        Some(7)
+    );
+
+    result_state.handle_key(Key::Home, &keymap);
+    assert_eq!(result_state.scroll, 0);
+}
+
+#[test]
+fn result_state_should_sort_numbers_by_value() {
+    let (sadari_env, path_hashmap) =
+        straight_ladder(&["a", "b", "c", "d"], &["10", "2", "Free", "1"]);
+    let mut result_state = ResultState::new();
+    result_state.order = ResultOrder::Result;
+
+    assert_eq!(
+        row_names(&result_state, &sadari_env, &path_hashmap),
+        vec!["d", "b", "a", "c"]
+    );
+}
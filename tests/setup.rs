@@ -0,0 +1,141 @@
+use rust_sadari_cli::helper::Key;
+use rust_sadari_cli::helper::{
+    self, SetupAction, SetupColumn, SetupFocus, SetupState, MAX_NUMBER_OF_BLOCKS,
+    MIN_NUMBER_OF_BLOCKS,
+};
+
+fn type_text(setup_state: &mut SetupState, text: &str) {
+    text.chars().for_each(|c| {
+        setup_state.handle_key(Key::Char(c));
+    });
+}
+
+fn type_text_edit(edit_state: &mut helper::EditState, text: &str) {
+    text.chars().for_each(|c| {
+        edit_state.handle_key(Key::Char(c));
+    });
+}
+
+#[test]
+fn setup_state_should_start_with_minimum_rows() {
+    let setup_state = SetupState::new();
+
+    assert_eq!(setup_state.number_of_rows(), MIN_NUMBER_OF_BLOCKS as usize);
+    assert_eq!(setup_state.focus, SetupFocus::Field(0, SetupColumn::Name));
+    assert!(setup_state.validate().is_err());
+}
+
+#[test]
+fn setup_state_should_not_start_with_empty_fields() {
+    let mut setup_state = SetupState::new();
+    type_text(&mut setup_state, "Alice");
+    setup_state.handle_key(Key::Down);
+    type_text(&mut setup_state, "Bob");
+
+    let err = setup_state.validate().unwrap_err();
+    assert!(err.contains("Result 1 is empty"));
+    assert_eq!(setup_state.handle_key(Key::Ctrl('s')), SetupAction::Stay);
+
+    setup_state.handle_key(Key::Ctrl('r'));
+    assert!(setup_state.validate().is_ok());
+    assert_eq!(setup_state.handle_key(Key::Ctrl('s')), SetupAction::Start);
+}
+
+#[test]
+fn setup_state_rows_has_upper_bound_limit() {
+    let mut setup_state = SetupState::new();
+
+    for _ in 0..(MAX_NUMBER_OF_BLOCKS * 2) {
+        setup_state.handle_key(Key::Char('\n'));
+    }
+
+    assert_eq!(setup_state.number_of_rows(), MAX_NUMBER_OF_BLOCKS as usize);
+    assert_eq!(setup_state.result_vec.len(), MAX_NUMBER_OF_BLOCKS as usize);
+}
+
+#[test]
+fn setup_state_remove_row_should_be_reflected_in_validation() {
+    let mut setup_state = SetupState::new();
+    setup_state.handle_key(Key::Ctrl('x'));
+
+    assert_eq!(setup_state.number_of_rows(), 1);
+    assert!(setup_state
+        .validate()
+        .unwrap_err()
+        .contains("Number of rows"));
+
+    // last row can not be removed
+    setup_state.handle_key(Key::Ctrl('x'));
+    assert_eq!(setup_state.number_of_rows(), 1);
+}
+
+#[test]
+fn setup_state_should_move_field_only_in_focused_column() {
+    let mut setup_state = SetupState::new();
+    type_text(&mut setup_state, "Alice");
+    setup_state.handle_key(Key::Char('\t'));
+    type_text(&mut setup_state, "win");
+    setup_state.handle_key(Key::Down);
+    type_text(&mut setup_state, "lose");
+    setup_state.handle_key(Key::Char('\t'));
+    type_text(&mut setup_state, "Bob");
+
+    setup_state.handle_key(Key::Ctrl('u'));
+
+    assert_eq!(setup_state.name_vec, vec!["Bob", "Alice"]);
+    assert_eq!(setup_state.result_vec, vec!["win", "lose"]);
+    assert_eq!(setup_state.focus, SetupFocus::Field(0, SetupColumn::Name));
+}
+
+#[test]
+fn setup_state_should_fill_sadari_env() {
+    let mut setup_state = SetupState::new();
+    type_text(&mut setup_state, " Alice ");
+    setup_state.handle_key(Key::Char('\n'));
+    type_text(&mut setup_state, "Bob");
+    setup_state.handle_key(Key::Backspace);
+    setup_state.handle_key(Key::Ctrl('r'));
+
+    let sadari_env = setup_state.sadari_env(helper::read_args(vec![String::new()].into_iter()));
+
+    assert_eq!(sadari_env.number_of_blocks, 3);
+    assert_eq!(sadari_env.name_vec, vec!["Alice", "Bo", ""]);
+    assert_eq!(sadari_env.result_vec, vec!["0", "1", "2"]);
+}
+
+#[test]
+fn edit_state_should_rename_only_target_box() {
+    let mut setup_state = SetupState::new();
+    type_text(&mut setup_state, "Alice");
+    setup_state.handle_key(Key::Down);
+    type_text(&mut setup_state, "Bob");
+    setup_state.handle_key(Key::Ctrl('r'));
+    let mut sadari_env = setup_state.sadari_env(helper::read_args(vec![String::new()].into_iter()));
+
+    let mut edit_state = helper::EditState::new(helper::EditTarget::Name, 1, &sadari_env);
+    assert_eq!(edit_state.text, "Bob");
+
+    edit_state.handle_key(Key::Backspace);
+    type_text_edit(&mut edit_state, "x");
+    assert_eq!(
+        edit_state.handle_key(Key::Char('\n')),
+        helper::EditAction::Apply
+    );
+    edit_state.apply(&mut sadari_env);
+
+    assert_eq!(sadari_env.name_vec, vec!["Alice", "Box"]);
+    assert_eq!(sadari_env.result_vec, vec!["0", "1"]);
+}
+
+#[test]
+fn edit_state_should_not_apply_empty_text() {
+    let sadari_env = helper::read_args(vec![String::new()].into_iter());
+    let mut edit_state = helper::EditState::new(helper::EditTarget::Result, 0, &sadari_env);
+
+    assert_eq!(edit_state.text, "");
+    assert_eq!(
+        edit_state.handle_key(Key::Char('\n')),
+        helper::EditAction::Stay
+    );
+    assert_eq!(edit_state.handle_key(Key::Esc), helper::EditAction::Cancel);
+}
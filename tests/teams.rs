@@ -0,0 +1,79 @@
+use rust_sadari_cli::helper;
+use std::{collections::HashMap, path::PathBuf};
+
+const DATA_PATH: &str = "tests/data";
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn team_labels_should_be_balanced() {
+    for number_of_players in 2..=12 {
+        for number_of_teams in 2..=number_of_players as u8 {
+            let labels = helper::calc_team_labels(number_of_players, number_of_teams);
+            assert_eq!(labels.len(), number_of_players);
+
+            let sizes: Vec<usize> = (1..=number_of_teams)
+                .map(|team| {
+                    let team = format!("Team {}", team);
+                    labels.iter().filter(|label| **label == team).count()
+                })
+                .collect();
+            assert_eq!(sizes.iter().sum::<usize>(), number_of_players);
+            assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+        }
+    }
+}
+
+#[test]
+fn teams_should_group_members_and_be_printed_as_json() {
+    let name_vec = to_strings(&["Alice", "Bob \"B\"", "Carol", "Dan"]);
+    let result_vec = helper::calc_team_labels(4, 2);
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    bridge_hashmap.insert(2, vec![5]);
+    let path_hashmap = helper::calc_path_hashmap(4, &bridge_hashmap, 10);
+
+    // Alice and Bob swap, Carol and Dan swap
+    let teams = helper::calc_teams(&path_hashmap, &name_vec, &result_vec);
+    assert_eq!(
+        teams,
+        vec![
+            (String::from("Team 1"), to_strings(&["Bob \"B\"", "Dan"])),
+            (String::from("Team 2"), to_strings(&["Alice", "Carol"])),
+        ]
+    );
+    assert_eq!(
+        helper::format_teams(&teams),
+        "Team 1: Bob \"B\", Dan\nTeam 2: Alice, Carol"
+    );
+
+    assert_eq!(
+        helper::format_json(&path_hashmap, &name_vec, &result_vec, true),
+        concat!(
+            r#"{"pairings":[{"name":"Alice","result":"Team 2"},"#,
+            r#"{"name":"Bob \"B\"","result":"Team 1"},"#,
+            r#"{"name":"Carol","result":"Team 2"},"#,
+            r#"{"name":"Dan","result":"Team 1"}],"#,
+            r#""teams":[{"name":"Team 1","members":["Bob \"B\"","Dan"]},"#,
+            r#"{"name":"Team 2","members":["Alice","Carol"]}]}"#
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "number of teams is larger than players")]
+fn read_args_should_panic_if_teams_are_more_than_players() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--teams"),
+        String::from("6"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
@@ -0,0 +1,88 @@
+use rust_sadari_cli::helper::{self, Theme};
+use std::path::PathBuf;
+use tui::style::{Color, Modifier, Style};
+
+const DATA_PATH: &str = "tests/data";
+
+#[test]
+fn theme_should_be_found_by_every_name() {
+    Theme::names().iter().for_each(|name| {
+        assert!(Theme::by_name(name).is_some(), "{}", name);
+    });
+    assert!(Theme::by_name("solarized").is_none());
+}
+
+#[test]
+fn parse_style_should_read_colors_and_modifiers() {
+    assert_eq!(
+        helper::parse_style("light_red on_black bold underlined").unwrap(),
+        Style::default()
+            .fg(Color::LightRed)
+            .bg(Color::Black)
+            .modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    );
+    assert_eq!(
+        helper::parse_style("208 on_#102030").unwrap(),
+        Style::default()
+            .fg(Color::Indexed(208))
+            .bg(Color::Rgb(16, 32, 48))
+    );
+    assert!(helper::parse_style("#12345").is_err());
+    assert!(helper::parse_style("blinking").is_err());
+}
+
+#[test]
+fn theme_parse_should_report_invalid_lines() {
+    assert!(Theme::dark()
+        .parse("base = sepia")
+        .unwrap_err()
+        .contains("line 1: unknown theme sepia"));
+    assert!(Theme::dark()
+        .parse("\nladder = red")
+        .unwrap_err()
+        .contains("line 2: unknown part ladder"));
+    assert!(Theme::dark()
+        .parse("path")
+        .unwrap_err()
+        .contains("expected `part = style`"));
+}
+
+#[test]
+fn read_args_should_read_theme_options_in_order() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/theme.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--theme"),
+        String::from("high-contrast"),
+        String::from("--theme-file"),
+        String::from(d.to_str().unwrap()),
+    ]
+    .into_iter();
+    let theme = helper::read_args(mock_args).theme;
+
+    assert_eq!(theme.rail, Theme::light().rail);
+    assert_eq!(
+        theme.path,
+        Style::default()
+            .fg(Color::Rgb(128, 0, 255))
+            .modifier(Modifier::BOLD)
+    );
+    assert_eq!(
+        theme.highlight,
+        Style::default().fg(Color::Black).bg(Color::LightYellow)
+    );
+}
+
+#[test]
+#[should_panic(expected = "unknown theme: sepia")]
+fn read_args_should_panic_with_unknown_theme() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--theme"),
+        String::from("sepia"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
@@ -1,4 +1,4 @@
-use rust_sadari_cli::helper;
+use rust_sadari_cli::helper::{self, AssignmentRule};
 use std::path::PathBuf;
 
 const DATA_PATH: &str = "tests/data";
@@ -42,7 +42,7 @@ fn read_file_should_read_at_most_two_lines() {
 
         let v = &sadari_env.name_vec;
         println!("sadari_env is {:?}", &sadari_env);
-        assert!(v.len() >= 1);
+        assert!(!v.is_empty());
     });
 }
 
@@ -120,7 +120,7 @@ fn read_file_should_generate_result_vec_if_not_provided() {
 
         assert_eq!(length, sadari_env.result_vec.len());
 
-        (0..length).into_iter().for_each(|x| {
+        (0..length).for_each(|x| {
             assert_eq!(
                 x,
                 sadari_env
@@ -169,3 +169,369 @@ fn read_file_name_and_result_length_has_lower_bound_limit() {
         helper::read_args(mock_args);
     });
 }
+
+#[test]
+fn read_args_should_read_fps_option() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--fps"),
+        String::from("30"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(sadari_env.frame_rate, 30);
+    assert_eq!(sadari_env.number_of_blocks, 5);
+}
+
+#[test]
+fn read_args_should_clamp_fps_option() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--fps"),
+        String::from("2000000"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(sadari_env.frame_rate, helper::MAX_FPS);
+}
+
+#[test]
+#[should_panic(expected = "--fps should be a positive number")]
+fn read_args_fps_option_should_be_positive() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--fps"),
+        String::from("0"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
+
+#[test]
+fn read_args_should_read_keymap_option() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/keymap.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--keymap"),
+        String::from(d.to_str().unwrap()),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(
+        sadari_env.keymap.action(helper::Key::Esc),
+        Some(helper::Action::Quit)
+    );
+    assert_eq!(sadari_env.keymap.action(helper::Key::Char('q')), None);
+    // every quit key stops a replay
+    assert_eq!(
+        sadari_env.keymap.exit_keys(),
+        vec![helper::Key::Esc, helper::Key::Ctrl('c')]
+    );
+}
+
+#[test]
+fn read_args_flags_should_not_take_a_value() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--ascii"),
+        String::from(d.to_str().unwrap()),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert!(sadari_env.ascii);
+    assert_eq!(sadari_env.number_of_blocks, 5);
+
+    let mock_args = vec![String::from("dummy path"), String::from("--unicode")].into_iter();
+    assert!(!helper::read_args(mock_args).ascii);
+}
+
+#[test]
+fn read_args_should_read_seed_and_report_options() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--seed"),
+        String::from("42"),
+        String::from("--report"),
+        String::from("csv"),
+        String::from("--report-file"),
+        String::from("out.csv"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(sadari_env.seed, Some(42));
+    assert_eq!(sadari_env.report, Some(helper::ReportFormat::Csv));
+    assert_eq!(sadari_env.report_file, Some(String::from("out.csv")));
+
+    // the same seed draws the same ladder
+    let bridge_hashmap = |rng: &mut rand::rngs::StdRng| {
+        helper::calc_bridge_hashmap(8, &helper::Geometry::default(), rng)
+    };
+    assert_eq!(
+        bridge_hashmap(&mut sadari_env.rng()),
+        bridge_hashmap(&mut sadari_env.rng())
+    );
+}
+
+#[test]
+#[should_panic(expected = "unknown report format: pdf")]
+fn read_args_report_format_should_be_known() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--report"),
+        String::from("pdf"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
+
+#[test]
+fn read_args_secret_santa_should_use_players_as_results() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--secret-santa"),
+        String::from("--forbid-pair"),
+        String::from("name1, name2"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(sadari_env.result_vec, sadari_env.name_vec);
+    assert_eq!(
+        sadari_env.forbidden_pairs,
+        vec![(String::from("name1"), String::from("name2"))]
+    );
+
+    let mut rng = sadari_env.rng();
+    for _ in 0..50 {
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let path_hashmap = helper::calc_path_hashmap(5, &bridge_hashmap, 10);
+
+        for (name_index, result_index) in helper::calc_pairings(&path_hashmap) {
+            let name = &sadari_env.name_vec[name_index as usize];
+            let result = &sadari_env.result_vec[result_index as usize];
+
+            assert_ne!(name, result);
+            // forbidden in both directions
+            assert!(!(name == "name1" && result == "name2"));
+            assert!(!(name == "name2" && result == "name1"));
+        }
+    }
+}
+
+#[test]
+fn secret_santa_should_report_impossible_forbidden_pairs() {
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from("--secret-santa"),
+        String::from("--forbid-pair"),
+        String::from("a, b"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args)
+        .number_of_blocks(2)
+        .name_vec(vec![String::from("a"), String::from("b")])
+        .fill_mode_results();
+
+    let err = sadari_env
+        .bridge_hashmap(&mut rand::thread_rng())
+        .unwrap_err();
+    assert!(err.contains("no assignment"));
+}
+
+#[test]
+#[should_panic(expected = "no assignment satisfies the secret santa rules")]
+fn read_args_should_panic_if_secret_santa_is_impossible() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_two_lines_1.txt", DATA_PATH));
+
+    // name1 has nobody left to draw
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--secret-santa"),
+        String::from("--forbid-pair"),
+        String::from("name1, name2"),
+        String::from("--forbid-pair"),
+        String::from("name3, name1"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
+
+#[test]
+fn read_args_should_read_constraints_section() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_constraints.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--constraint"),
+        String::from("Eve != Free"),
+    ]
+    .into_iter();
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(sadari_env.result_vec.len(), 5);
+    assert_eq!(
+        sadari_env.constraints,
+        vec![
+            AssignmentRule::Forbid(String::from("Alice"), String::from("Night")),
+            AssignmentRule::Require(String::from("Bob"), String::from("Window")),
+            AssignmentRule::Require(String::from("Carol"), String::from("Aisle")),
+            AssignmentRule::Require(String::from("Dan"), String::from("Day")),
+            AssignmentRule::Forbid(String::from("Eve"), String::from("Free")),
+        ]
+    );
+
+    // Alice gets Free and Eve gets Night, the only assignment left
+    let mut rng = sadari_env.rng();
+    for _ in 0..20 {
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let path_hashmap = helper::calc_path_hashmap(5, &bridge_hashmap, 10);
+
+        assert_eq!(
+            helper::calc_result_lines(&path_hashmap, &sadari_env.name_vec, &sadari_env.result_vec),
+            vec![
+                "Alice: Free",
+                "Bob: Window",
+                "Carol: Aisle",
+                "Dan: Day",
+                "Eve: Night"
+            ]
+        );
+    }
+}
+
+#[test]
+fn constraint_parse_should_read_forbidden_and_required_results() {
+    assert_eq!(
+        AssignmentRule::parse(" Alice != night shift "),
+        Ok(AssignmentRule::Forbid(
+            String::from("Alice"),
+            String::from("night shift")
+        ))
+    );
+    assert_eq!(
+        AssignmentRule::parse("Bob=window"),
+        Ok(AssignmentRule::Require(
+            String::from("Bob"),
+            String::from("window")
+        ))
+    );
+    assert!(AssignmentRule::parse("Bob -> window").is_err());
+    assert!(AssignmentRule::parse(" = window").is_err());
+}
+
+#[test]
+#[should_panic(expected = "has an unknown result: Sunday")]
+fn read_args_should_panic_if_constraint_is_unknown() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_constraints.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--constraint"),
+        String::from("Eve = Sunday"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
+
+#[test]
+#[should_panic(expected = "no assignment satisfies the constraints")]
+fn read_args_should_panic_if_constraints_are_impossible() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_constraints.txt", DATA_PATH));
+
+    // Bob already has Window
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--constraint"),
+        String::from("Eve = Window"),
+    ]
+    .into_iter();
+    helper::read_args(mock_args);
+}
+
+#[test]
+fn read_args_should_read_geometry() {
+    let mock_args = vec![
+        "dummy path",
+        "--rows",
+        "16",
+        "--min-bridges",
+        "1",
+        "--max-bridges",
+        "9",
+        "--density",
+        "40",
+        "--share-rows",
+        "--no-edge-rows",
+    ]
+    .into_iter()
+    .map(String::from);
+    let sadari_env = helper::read_args(mock_args);
+
+    assert_eq!(
+        sadari_env.geometry,
+        helper::Geometry {
+            rows: 16,
+            min_bridges: 1,
+            max_bridges: 9,
+            density: Some(40),
+            share_rows: true,
+            edge_rows: false,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "min bridges 4 is larger than max bridges 3")]
+fn read_args_should_panic_if_min_bridges_is_larger_than_max() {
+    let mock_args = vec!["dummy path", "--min-bridges", "4", "--max-bridges", "3"]
+        .into_iter()
+        .map(String::from);
+    helper::read_args(mock_args);
+}
+
+#[test]
+fn is_utf8_locale_should_be_ascii_only_for_set_non_utf8_locale() {
+    assert!(helper::is_utf8_locale(Some("en_US.UTF-8")));
+    assert!(helper::is_utf8_locale(Some("ko_KR.utf8")));
+    assert!(!helper::is_utf8_locale(Some("C")));
+    assert!(!helper::is_utf8_locale(Some("POSIX")));
+    // nothing set keeps the unicode lines
+    assert!(helper::is_utf8_locale(None));
+}
+
+#[test]
+#[should_panic(
+    expected = "min bridges 2 and max bridges 5 of neighbouring gaps do not fit in the 6 rows"
+)]
+fn read_args_should_panic_if_bridges_of_neighbouring_gaps_do_not_fit_in_the_rows() {
+    let mock_args = vec!["dummy path", "--rows", "6"]
+        .into_iter()
+        .map(String::from);
+    helper::read_args(mock_args);
+}
@@ -0,0 +1,119 @@
+use rust_sadari_cli::helper::{self, Round};
+use std::{collections::HashMap, path::PathBuf};
+
+const DATA_PATH: &str = "tests/data";
+
+fn to_strings(vec: &[&str]) -> Vec<String> {
+    vec.iter().map(|x| String::from(*x)).collect()
+}
+
+#[test]
+fn round_should_split_players_into_advanced_and_out() {
+    assert_eq!(
+        helper::calc_round_results(5),
+        to_strings(&["out", "advance", "out", "advance", "out"])
+    );
+
+    let name_vec = to_strings(&["Alice", "Bob", "Carol", "Dan"]);
+    let result_vec = helper::calc_round_results(4);
+    let mut bridge_hashmap: HashMap<u16, Vec<u16>> = HashMap::new();
+    bridge_hashmap.insert(0, vec![2]);
+    let path_hashmap = helper::calc_path_hashmap(4, &bridge_hashmap, 10);
+
+    // Alice and Bob swap
+    let round = Round::new(1, &path_hashmap, &name_vec, &result_vec).unwrap();
+    assert_eq!(round.advanced, to_strings(&["Alice", "Dan"]));
+    assert_eq!(round.out, to_strings(&["Bob", "Carol"]));
+    assert!(!round.is_final());
+
+    let final_round = Round {
+        number: 2,
+        advanced: to_strings(&["Dan"]),
+        out: to_strings(&["Alice"]),
+    };
+    let rounds = vec![round, final_round];
+    assert_eq!(
+        helper::format_bracket(&rounds),
+        vec![
+            "Round 1: Alice, Dan advance, Bob, Carol out",
+            "Round 2: Dan advance, Alice out",
+            "Winner: Dan",
+        ]
+    );
+    assert_eq!(
+        helper::format_bracket_json(&rounds),
+        concat!(
+            r#"{"rounds":[{"round":1,"advanced":["Alice","Dan"],"out":["Bob","Carol"]},"#,
+            r#"{"round":2,"advanced":["Dan"],"out":["Alice"]}],"winner":"Dan"}"#
+        )
+    );
+}
+
+#[test]
+fn round_should_fail_if_nobody_advanced() {
+    let name_vec = to_strings(&["Alice", "Bob"]);
+    let result_vec = to_strings(&["out", "out"]);
+    let path_hashmap = helper::calc_path_hashmap(2, &HashMap::new(), 10);
+
+    assert_eq!(
+        Round::new(3, &path_hashmap, &name_vec, &result_vec),
+        Err(String::from("nobody advanced in round 3"))
+    );
+}
+
+#[test]
+fn next_round_should_carry_players_who_advanced() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("{}/input_same_length.txt", DATA_PATH));
+
+    let mock_args = vec![
+        String::from("dummy path"),
+        String::from(d.to_str().unwrap()),
+        String::from("--tournament"),
+        String::from("--seed"),
+        String::from("7"),
+    ]
+    .into_iter();
+    let mut sadari_env = helper::read_args(mock_args);
+    assert_eq!(sadari_env.result_vec, helper::calc_round_results(5));
+
+    let mut rng = sadari_env.rng();
+    loop {
+        let bridge_hashmap = sadari_env.bridge_hashmap(&mut rng).unwrap();
+        let path_hashmap = helper::calc_path_hashmap(
+            sadari_env.number_of_blocks,
+            &bridge_hashmap,
+            sadari_env.geometry.rows,
+        );
+        let round = Round::new(
+            sadari_env.round,
+            &path_hashmap,
+            &sadari_env.name_vec,
+            &sadari_env.result_vec,
+        )
+        .unwrap();
+        assert_eq!(
+            round.advanced.len() + round.out.len(),
+            sadari_env.name_vec.len()
+        );
+
+        if round.is_final() {
+            sadari_env.rounds.push(round);
+            break;
+        }
+
+        let advanced = round.advanced.clone();
+        sadari_env = sadari_env.next_round(round);
+        assert_eq!(sadari_env.name_vec, advanced);
+        assert_eq!(sadari_env.number_of_blocks as usize, advanced.len());
+        assert_eq!(
+            sadari_env.result_vec,
+            helper::calc_round_results(advanced.len())
+        );
+    }
+
+    // 5 -> 2 -> 1
+    assert_eq!(sadari_env.rounds.len(), 2);
+    assert_eq!(sadari_env.round, 2);
+    assert!(sadari_env.winner().is_some());
+}